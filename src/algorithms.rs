@@ -1,156 +1,335 @@
-#![allow(clippy::needless_range_loop)]
-use crate::config::Config;
-
-/// Returns a score from 0 to 1 depending on the local alignment of the two string sequences
-pub fn smith_waterman(seq_1: &str, seq_2: &str) -> f64 {
-    let match_score: i16 = Config::get().match_score;
-    let mismatch_penalty: i16 = Config::get().mismatch_penalty;
-    let gap_penalty: i16 = Config::get().gap_penalty;
-
-    // initialization, seq_1 on the left and seq_2 on the top
-    let seq_1: Vec<char> = seq_1.chars().collect();
-    let seq_2: Vec<char> = seq_2.chars().collect();
-    let mut matrix: Vec<Vec<i16>> = vec![vec![0; seq_2.len() + 1]; seq_1.len() + 1];
-
-    // matrix filling
-    let mut total_score: i16 = 0;
-    let mut largest_score_index: (usize, usize) = (0, 0);
-    for i in 1..seq_1.len() + 1 {
-        for j in 1..seq_2.len() + 1 {
-            matrix[i][j] = {
-                let score = [
-                    0,
-                    matrix[i][j - 1] + gap_penalty,
-                    matrix[i - 1][j] + gap_penalty,
-                    if seq_1[i - 1] == seq_2[j - 1] {
-                        matrix[i - 1][j - 1] + match_score
-                    } else {
-                        matrix[i - 1][j - 1] + mismatch_penalty
-                    },
-                ]
-                .into_iter()
-                .max()
-                .unwrap(); // safe unwrap
-                if score > total_score {
-                    total_score = score;
-                    largest_score_index = (i, j);
-                }
-                score
-            }
+use crate::{
+    config::Config,
+    data::{
+        Entry,
+        syrtime::{Blocs, SyrDate},
+    },
+};
+
+/// Scores entry names and aliases against a query
+///
+/// The scoring parameters are read from the configuration once, and the dynamic programming buffers are reused between calls,
+/// only two rows of each alignment matrix are ever kept in memory.
+pub struct Matcher {
+    match_score: i32,
+    mismatch_penalty: i32,
+    gap_penalty: i32,
+    sw_nw_ratio: f64,
+    prefix_bonus: f64,
+    word_boundary_bonus: f64,
+    acronym_bonus: f64,
+    recency_weight: f64,
+    frequency_weight: f64,
+    seq_1: Vec<char>,
+    seq_2: Vec<char>,
+    previous_row: Vec<i32>,
+    current_row: Vec<i32>,
+}
+
+impl Matcher {
+    /// Number of days over which recency and frequency are measured
+    const ACTIVITY_WINDOW: i64 = 28;
+
+    pub fn new(config: &Config) -> Self {
+        Self {
+            match_score: config.match_score,
+            mismatch_penalty: config.mismatch_penalty,
+            gap_penalty: config.gap_penalty,
+            sw_nw_ratio: config.sw_nw_ratio,
+            prefix_bonus: config.prefix_bonus,
+            word_boundary_bonus: config.word_boundary_bonus,
+            acronym_bonus: config.acronym_bonus,
+            recency_weight: config.recency_weight,
+            frequency_weight: config.frequency_weight,
+            seq_1: Vec::new(),
+            seq_2: Vec::new(),
+            previous_row: Vec::new(),
+            current_row: Vec::new(),
         }
     }
 
-    // traceback
-    let (mut i, mut j) = largest_score_index;
-    let mut score = total_score;
-    while score != 0 {
-        score = [
-            (i, j - 1, matrix[i][j - 1]),
-            (i - 1, j, matrix[i - 1][j]),
-            (i - 1, j - 1, matrix[i - 1][j - 1]),
-        ]
-        .into_iter()
-        .fold(0, |acc, (_i, _j, val)| {
-            if val > acc {
-                i = _i;
-                j = _j;
-                val
-            } else {
-                acc
+    /// Returns the best score of the entry's name and aliases, including the activity boost
+    pub fn score_entry(&mut self, entry: &Entry, query: &str, today: &SyrDate) -> f64 {
+        let text_score = std::iter::once(&entry.name)
+            .chain(entry.aliases.iter())
+            .map(|string| self.score(string, query))
+            .fold(f64::NEG_INFINITY, f64::max);
+        text_score + self.activity_boost(&entry.blocs, today)
+    }
+
+    /// Returns the weighted alignment score of the candidate and the query, plus any prefix, word boundary or acronym bonus
+    pub fn score(&mut self, candidate: &str, query: &str) -> f64 {
+        self.sw_nw_ratio * self.smith_waterman(candidate, query)
+            + (1.0 - self.sw_nw_ratio) * self.needleman_wunsch(candidate, query)
+            + self.bonus(candidate, query)
+    }
+
+    /// Returns a score from 0 to 1 depending on the local alignment of the two string sequences
+    pub fn smith_waterman(&mut self, seq_1: &str, seq_2: &str) -> f64 {
+        self.load(seq_1, seq_2);
+        if self.seq_1.is_empty() || self.seq_2.is_empty() || self.match_score <= 0 {
+            return 0.0;
+        }
+
+        // seq_1 on the left and seq_2 on the top, only the previous and current rows are kept
+        let mut best_score: i32 = 0;
+        for i in 1..=self.seq_1.len() {
+            self.current_row[0] = 0;
+            for j in 1..=self.seq_2.len() {
+                let diagonal = self.previous_row[j - 1] + self.substitution(i, j);
+                let score = diagonal
+                    .max(self.previous_row[j] + self.gap_penalty)
+                    .max(self.current_row[j - 1] + self.gap_penalty)
+                    .max(0);
+                self.current_row[j] = score;
+                best_score = best_score.max(score);
             }
-        });
-        total_score += score;
+            std::mem::swap(&mut self.previous_row, &mut self.current_row);
+        }
+
+        // normalization, a perfect local alignment matches every character of the shortest sequence
+        let max_score = self.seq_1.len().min(self.seq_2.len()) as f64 * self.match_score as f64;
+        (best_score as f64 / max_score).clamp(0.0, 1.0)
     }
 
-    // normalization
-    let max_score = {
-        let val = [match_score, mismatch_penalty, gap_penalty].into_iter().max().unwrap().abs();
-        let n = std::cmp::min(seq_1.len(), seq_2.len()) as i16 * val;
-        (n * (n + 1)) / 2
-    };
+    /// Returns a score from -1 to 1 depending on the global alignment of the two string sequences
+    pub fn needleman_wunsch(&mut self, seq_1: &str, seq_2: &str) -> f64 {
+        self.load(seq_1, seq_2);
+        if self.seq_1.is_empty() && self.seq_2.is_empty() {
+            return 0.0;
+        }
 
-    total_score as f64 / max_score as f64
-}
+        // seq_1 on the left and seq_2 on the top, only the previous and current rows are kept
+        for (j, cell) in self.previous_row.iter_mut().enumerate() {
+            *cell = j as i32 * self.gap_penalty;
+        }
+        for i in 1..=self.seq_1.len() {
+            self.current_row[0] = i as i32 * self.gap_penalty;
+            for j in 1..=self.seq_2.len() {
+                let diagonal = self.previous_row[j - 1] + self.substitution(i, j);
+                self.current_row[j] = diagonal
+                    .max(self.previous_row[j] + self.gap_penalty)
+                    .max(self.current_row[j - 1] + self.gap_penalty);
+            }
+            std::mem::swap(&mut self.previous_row, &mut self.current_row);
+        }
+        let total_score = self.previous_row[self.seq_2.len()];
 
-/// Returns a score from -1 to 1 depending on the global alignment of the two string sequences
-pub fn needleman_wunsch(seq_1: &str, seq_2: &str) -> f64 {
-    let match_score: i16 = Config::get().match_score;
-    let mismatch_penalty: i16 = Config::get().mismatch_penalty;
-    let gap_penalty: i16 = Config::get().gap_penalty;
-
-    // initialization, seq_1 on the left and seq_2 on the top
-    let seq_1: Vec<char> = seq_1.chars().collect();
-    let seq_2: Vec<char> = seq_2.chars().collect();
-    let mut matrix: Vec<Vec<i16>> = vec![vec![0; seq_2.len() + 1]; seq_1.len() + 1];
-
-    // matrix filling
-    for j in 1..seq_2.len() + 1 {
-        matrix[0][j] = j as i16 * gap_penalty
-    }
-    for i in 1..seq_1.len() + 1 {
-        matrix[i][0] = i as i16 * gap_penalty
-    }
-    for i in 1..seq_1.len() + 1 {
-        for j in 1..seq_2.len() + 1 {
-            matrix[i][j] = {
-                [
-                    matrix[i][j - 1] + gap_penalty,
-                    matrix[i - 1][j] + gap_penalty,
-                    if seq_1[i - 1] == seq_2[j - 1] {
-                        matrix[i - 1][j - 1] + match_score
-                    } else {
-                        matrix[i - 1][j - 1] + mismatch_penalty
-                    },
-                ]
-                .into_iter()
-                .max()
-                .unwrap() // safe unwrap
+        // normalization, clamped in case the user sets something absurd like a negative match score
+        let normalized = match total_score {
+            1.. => {
+                let max_pos_score = self.seq_1.len().max(self.seq_2.len()) as f64 * self.match_score.max(1) as f64;
+                total_score as f64 / max_pos_score
+            }
+            0 => 0.0,
+            ..=-1 => {
+                let worst_penalty = self.mismatch_penalty.abs().max(self.gap_penalty.abs()).max(1);
+                let max_neg_score = (self.seq_1.len() + self.seq_2.len()) as f64 * worst_penalty as f64;
+                total_score as f64 / max_neg_score
             }
+        };
+        normalized.clamp(-1.0, 1.0)
+    }
+
+    /// Returns the largest applicable bonus, a prefix of the whole candidate is worth more than a prefix of one of its words
+    pub fn bonus(&self, candidate: &str, query: &str) -> f64 {
+        if query.is_empty() {
+            return 0.0;
+        }
+        let mut bonus: f64 = 0.0;
+        if candidate.starts_with(query) {
+            bonus = bonus.max(self.prefix_bonus);
+        }
+        if words(candidate).skip(1).any(|word| word.starts_with(query)) {
+            bonus = bonus.max(self.word_boundary_bonus);
         }
+        let query_len = query.chars().count();
+        if query_len > 1 && words(candidate).count() >= query_len {
+            let initials: String = words(candidate).filter_map(|word| word.chars().next()).collect();
+            if initials.starts_with(query) {
+                bonus = bonus.max(self.acronym_bonus);
+            }
+        }
+        bonus
+    }
+
+    /// Returns a boost favouring entries that were tracked recently and often
+    pub fn activity_boost(&self, blocs: &Blocs, today: &SyrDate) -> f64 {
+        let window_start: SyrDate = today.saturating_sub(jiff::ToSpan::days(Self::ACTIVITY_WINDOW)).into();
+        let mut active_days: i64 = 0;
+        let mut last_active: Option<SyrDate> = None;
+        for (date, _) in blocs.range(window_start..=*today).filter(|(_, duration)| **duration > 0.0) {
+            active_days += 1;
+            last_active = Some(*date);
+        }
+        let Some(last_active) = last_active else {
+            return 0.0;
+        };
+        let days_since = (**today - *last_active).get_days() as f64;
+        let recency = 1.0 - days_since / Self::ACTIVITY_WINDOW as f64;
+        let frequency = active_days as f64 / (Self::ACTIVITY_WINDOW + 1) as f64;
+        self.recency_weight * recency + self.frequency_weight * frequency
+    }
+
+    fn load(&mut self, seq_1: &str, seq_2: &str) {
+        self.seq_1.clear();
+        self.seq_1.extend(seq_1.chars());
+        self.seq_2.clear();
+        self.seq_2.extend(seq_2.chars());
+        self.previous_row.clear();
+        self.previous_row.resize(self.seq_2.len() + 1, 0);
+        self.current_row.clear();
+        self.current_row.resize(self.seq_2.len() + 1, 0);
     }
 
-    // traceback
-    let (mut i, mut j) = (seq_1.len(), seq_2.len());
-    let mut total_score: i16 = matrix[i][j];
-    while j != 0 && i != 0 {
-        if seq_1[i - 1] == seq_2[j - 1] {
-            i -= 1;
-            j -= 1;
+    fn substitution(&self, i: usize, j: usize) -> i32 {
+        if self.seq_1[i - 1] == self.seq_2[j - 1] {
+            self.match_score
         } else {
-            // this only considers a single path, gives priority to going 'upwards'
-            if matrix[i][j - 1] > matrix[i - 1][j] {
-                j -= 1;
-            } else {
-                i -= 1
-            }
+            self.mismatch_penalty
+        }
+    }
+}
+
+/// Splits a name into its words, anything that isn't alphanumeric is a separator
+fn words(string: &str) -> impl Iterator<Item = &str> {
+    string.split(|chr: char| !chr.is_alphanumeric()).filter(|word| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    const SYLLABLES: [&str; 16] = [
+        "MA", "TH", "PHY", "SIC", "BIO", "LO", "GY", "CHEM", "IS", "TRY", "HIS", "TO", "RY", "ART", "CODE", "NET",
+    ];
+
+    /// Deterministically generates entries such as 'BIOLO-201' with aliases
+    fn synthetic_entries(count: usize) -> Vec<Entry> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        };
+        (0..count)
+            .map(|idx| {
+                let word: String = (0..2 + next(3)).map(|_| SYLLABLES[next(SYLLABLES.len())]).collect();
+                let name = format!("{word}-{idx}");
+                let aliases = (0..next(3))
+                    .map(|_| (0..1 + next(3)).map(|_| SYLLABLES[next(SYLLABLES.len())]).collect::<String>())
+                    .collect();
+                Entry::new(name, aliases, Blocs::default(), true)
+            })
+            .collect()
+    }
+
+    fn today() -> SyrDate {
+        jiff::civil::date(2026, 3, 15).into()
+    }
+
+    fn ranking(entries: &[Entry], query: &str) -> Vec<String> {
+        let mut matcher = Matcher::new(&Config::default());
+        entries
+            .iter()
+            .map(|entry| (matcher.score_entry(entry, query, &today()), entry.name.clone()))
+            .sorted_by(|(a, _), (b, _)| b.total_cmp(a))
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    #[test]
+    fn scores_are_normalized() {
+        let mut matcher = Matcher::new(&Config::default());
+        for (a, b) in [("STAT-110", "STAT-110"), ("STAT", "PROBSTAT"), ("A", "ZZZZZZZZZZZZ"), ("", "ABC")] {
+            let sw = matcher.smith_waterman(a, b);
+            let nw = matcher.needleman_wunsch(a, b);
+            assert!((0.0..=1.0).contains(&sw), "{a} {b} {sw}");
+            assert!((-1.0..=1.0).contains(&nw), "{a} {b} {nw}");
         }
-        total_score += matrix[i][j]
-    }
-    // goes to (0, 0) when reaching an edge
-    let i_gp = i as i16 * gap_penalty;
-    let j_gp = j as i16 * gap_penalty;
-    total_score += i_gp * (i_gp + 1) / 2 + j_gp * (j_gp + 1) / 2;
-
-    // normalization
-    // paranoid, but this should mathematically ensure the result is between -1 and 1 even if the user does something absurd like set the match_score to a negative integer
-    // or if they have absurdly high penalties, etc...
-    match total_score {
-        1.. => {
-            let max_pos_score = {
-                let val = [match_score, mismatch_penalty, gap_penalty].into_iter().max().unwrap().abs();
-                let n = std::cmp::max(seq_1.len(), seq_2.len()) as i16 * val;
-                (n * (n + 1)) / 2
-            };
-            total_score as f64 / max_pos_score as f64
-        }
-        0 => 0.0,
-        ..=-1 => {
-            let max_neg_score = {
-                let val = [match_score, mismatch_penalty, gap_penalty].into_iter().min().unwrap().abs();
-                let n = std::cmp::max(seq_1.len(), seq_2.len()) as i16 * val;
-                (n * (n + 1)) / 2
-            };
-            total_score as f64 / max_neg_score as f64
+        assert_eq!(matcher.smith_waterman("STAT-110", "STAT-110"), 1.0);
+        assert_eq!(matcher.needleman_wunsch("STAT-110", "STAT-110"), 1.0);
+    }
+
+    #[test]
+    fn long_names_do_not_overflow() {
+        let mut matcher = Matcher::new(&Config {
+            match_score: 1000,
+            ..Config::default()
+        });
+        let long = "AB".repeat(200);
+        assert_eq!(matcher.smith_waterman(&long, &long), 1.0);
+        assert_eq!(matcher.needleman_wunsch(&long, &long), 1.0);
+    }
+
+    #[test]
+    fn bonuses() {
+        let matcher = Matcher::new(&Config::default());
+        let config = Config::default();
+        assert_eq!(matcher.bonus("STAT-110", "STAT"), config.prefix_bonus);
+        assert_eq!(matcher.bonus("INTRO-STATISTICS", "STAT"), config.word_boundary_bonus);
+        assert_eq!(matcher.bonus("MACHINE-LEARNING", "ML"), config.acronym_bonus);
+        assert_eq!(matcher.bonus("MACHINE-LEARNING", "XY"), 0.0);
+    }
+
+    #[test]
+    fn exact_name_ranks_first() {
+        let entries = synthetic_entries(3000);
+        for entry in entries.iter().step_by(250) {
+            assert_eq!(ranking(&entries, &entry.name)[0], entry.name);
+        }
+    }
+
+    #[test]
+    fn prefix_and_acronym_rank_first() {
+        let mut entries = synthetic_entries(3000);
+        entries.push(Entry::new("QUANTUM-FIELD-THEORY".to_string(), Vec::new(), Blocs::default(), true));
+        entries.push(Entry::new(
+            "ZEBRAFISH".to_string(),
+            vec!["DANIO".to_string()],
+            Blocs::default(),
+            true,
+        ));
+        assert_eq!(ranking(&entries, "QFT")[0], "QUANTUM-FIELD-THEORY");
+        assert_eq!(ranking(&entries, "ZEBRA")[0], "ZEBRAFISH");
+        assert_eq!(ranking(&entries, "DAN")[0], "ZEBRAFISH");
+    }
+
+    #[test]
+    fn recent_and_frequent_entries_break_ties() {
+        let mut blocs = Blocs::default();
+        for days_back in [0, 1, 2, 5, 8] {
+            blocs.insert(today().saturating_sub(jiff::ToSpan::days(days_back)).into(), 3600.0);
+        }
+        let entries = vec![
+            Entry::new("THESIS-OLD".to_string(), Vec::new(), Blocs::default(), true),
+            Entry::new("THESIS-NEW".to_string(), Vec::new(), blocs, true),
+        ];
+        assert_eq!(ranking(&entries, "THESIS")[0], "THESIS-NEW");
+    }
+
+    /// Run with `cargo test --release -- --ignored --nocapture` to measure the matcher
+    #[test]
+    #[ignore]
+    fn benchmark() {
+        let entries = synthetic_entries(5000);
+        let queries = ["MATH", "BIOLO", "CODENET", "PHYSIC-42", "HSTRY"];
+        let start = std::time::Instant::now();
+        let iterations = 10;
+        for _ in 0..iterations {
+            for query in queries {
+                std::hint::black_box(ranking(&entries, query));
+            }
         }
+        let elapsed = start.elapsed();
+        println!(
+            "{} queries over {} entries in {:?}, {:?} per query",
+            iterations * queries.len(),
+            entries.len(),
+            elapsed,
+            elapsed / (iterations * queries.len()) as u32
+        );
     }
 }
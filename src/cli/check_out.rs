@@ -13,7 +13,7 @@ pub(super) fn subcommand() -> Command {
                 .long("cancel")
                 .required(false)
                 .action(ArgAction::SetTrue)
-                .exclusive(true),
+                .conflicts_with("check"),
         )
         .arg(
             Arg::new("check")
@@ -21,66 +21,35 @@ pub(super) fn subcommand() -> Command {
                 .short('c')
                 .long("check")
                 .required(false)
-                .action(ArgAction::SetTrue)
-                .exclusive(true),
+                .action(ArgAction::SetTrue),
         )
 }
 
 pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
-    let filepaths = std::fs::read_dir(Dirs::get().data_dir())?
-        .filter_map(|filepath| {
-            let filepath = match filepath {
-                Ok(e) => e,
-                Err(err) => {
-                    eprintln!("Warning: {}", err);
-                    return None;
-                }
-            }
-            .path();
-
-            if filepath.extension()?.to_str()? == "cin" {
-                Some(filepath)
-            } else {
-                None
-            }
-        })
-        .collect_vec();
-    {};
-
-    let filepath = match filepaths.len() {
-        0 => bail!("Failed to find a single checked-in entry"),
-        1 => filepaths[0].clone(),
-        _ => bail!("Multiple checked-in entries found"),
-    };
+    let check_in = CheckIn::find()?.ok_or_eyre("Failed to find a single checked-in entry")?;
 
     if arg_matches.get_flag("cancel") {
-        std::fs::remove_file(&filepath)?;
-        return Ok(());
-    }
-
-    let mut buffer: Vec<u8> = Vec::new();
-    std::fs::OpenOptions::new().read(true).open(&filepath)?.read_to_end(&mut buffer)?;
-    let timestamp: jiff::Timestamp = ijson::from_value(&serde_json::from_slice(&buffer)?)?;
-    let elapsed = jiff::Timestamp::now().since(timestamp)?.abs().total(jiff::Unit::Second)?;
-
-    if arg_matches.get_flag("check") {
-        println!("{} {}", ARROW.green(), elapsed.ms_str());
-        return Ok(());
+        return check_in.cancel();
     }
 
-    let name = filepath
-        .file_stem()
-        .ok_or_eyre("Invalid file name")?
-        .to_str()
-        .ok_or_eyre("Invalid file name")?;
     let mut entry = entries
         .iter()
-        .find(|entry| entry.name == name)
+        .find(|entry| entry.name == check_in.name)
         .ok_or_eyre("Failed to find an entry that matches the checked-in name")?
         .clone();
 
+    if arg_matches.get_flag("check") {
+        let elapsed = check_in.elapsed()?;
+        let output = OutputFormat::from_arg_matches(arg_matches);
+        if output.is_pretty() {
+            println!("{} {}", ARROW.green(), elapsed.live_str());
+            return Ok(());
+        }
+        return crate::output::emit(output, &[Record::new(&entry, Some(today), elapsed)]);
+    }
+
     let past = entry.get_bloc_duration(today);
-    entry.increase_bloc_duration(today, elapsed);
+    let elapsed = check_in.complete(&mut entry, today)?;
     println!(
         "{} {} {} {}",
         past.s_str(),
@@ -88,8 +57,6 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
         (past + elapsed).s_str(),
         format!("| ({} - {})", entry.print_name_and_first_alias(), today).dim()
     );
-    entry.save()?;
-    std::fs::remove_file(&filepath)?;
 
     Ok(())
 }
@@ -0,0 +1,347 @@
+use super::*;
+use clap::{ValueEnum, builder::PossibleValue};
+
+/// The calendar periods that can be compared
+#[derive(Debug, Clone, Copy)]
+enum Period {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl ValueEnum for Period {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Day, Self::Week, Self::Month, Self::Year]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Day => PossibleValue::new("day").alias("daily"),
+            Self::Week => PossibleValue::new("week").alias("weekly"),
+            Self::Month => PossibleValue::new("month").alias("monthly"),
+            Self::Year => PossibleValue::new("year").alias("yearly"),
+        })
+    }
+}
+
+impl Period {
+    /// Returns the whole period containing the date
+    fn span_of(&self, date: SyrDate) -> SyrSpan {
+        let granularity = match self {
+            Self::Day => Granularity::Day,
+            Self::Week => Granularity::Week,
+            Self::Month => Granularity::Month,
+            Self::Year => return SyrSpan::from_start_and_end(date.first_of_year(), date.last_of_year()),
+        };
+        let bucket = granularity.bucket(date);
+        SyrSpan::from_start_and_end(*bucket.start, *bucket.end)
+    }
+}
+
+/// What the span is compared against
+#[derive(Debug, Clone, Copy, Default)]
+enum Baseline {
+    /// The period right before, or the span of the same length right before
+    #[default]
+    Previous,
+    /// The same period or span one year earlier
+    LastYear,
+}
+
+impl ValueEnum for Baseline {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Previous, Self::LastYear]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Previous => PossibleValue::new("previous").help("The period right before"),
+            Self::LastYear => PossibleValue::new("last-year").help("The same period one year earlier"),
+        })
+    }
+}
+
+pub(super) fn subcommand() -> Command {
+    Command::new("compare")
+        .aliases(["cmp", "versus", "vs"])
+        .about("Compare the time tracked over two spans")
+        .long_about("This subcommand is used to compare the time tracked by every single entry over two spans side by side, the current week and the previous one by default\naliases: 'cmp', 'versus', 'vs'")
+        .arg(
+            Arg::new("period")
+                .help("The period compared, the current one unless periods back are specified")
+                .short('p')
+                .long("period")
+                .value_parser(value_parser!(Period))
+                .conflicts_with("custom-span")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("periods-back")
+                .help("The number of periods back to compare")
+                .short('b')
+                .long("back")
+                .alias("periods-back")
+                .value_parser(value_parser!(usize))
+                .conflicts_with("custom-span")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("against")
+                .help("What the span is compared against")
+                .short('a')
+                .long("against")
+                .value_parser(value_parser!(Baseline))
+                .conflicts_with("vs-start-date")
+                .action(ArgAction::Set),
+        )
+        .args(span_args())
+        .arg(
+            Arg::new("vs-start-date")
+                .help("The start date of the span compared against")
+                .long("vs-start")
+                .requires("custom-span")
+                .action(ArgAction::Set)
+                .allow_hyphen_values(true)
+                .value_parser(clap::value_parser!(SyrDate)),
+        )
+        .arg(
+            Arg::new("vs-end-date")
+                .help("The end date of the span compared against, the span being as long as the custom one by default")
+                .long("vs-end")
+                .requires("vs-start-date")
+                .action(ArgAction::Set)
+                .allow_hyphen_values(true)
+                .value_parser(clap::value_parser!(SyrDate)),
+        )
+        .arg(
+            Arg::new("to-date")
+                .help("Only compare the days elapsed so far, and as many days of the span compared against")
+                .long("to-date")
+                .action(ArgAction::SetTrue),
+        )
+        .group(ArgGroup::new("custom-span").args(["days-back", "start-date"]))
+}
+
+/// Returns the span compared and the span it is compared against
+fn spans(arg_matches: &ArgMatches, today: &SyrDate) -> Result<(SyrSpan, SyrSpan)> {
+    let against = arg_matches.get_one::<Baseline>("against").copied().unwrap_or_default();
+
+    let (mut current, mut baseline) = if let Some(current) = date_span(arg_matches, today)? {
+        let length = (current.end - current.start).get_days() as i64;
+        let baseline = match arg_matches.get_one::<SyrDate>("vs-start-date") {
+            Some(vs_start_date) => {
+                let vs_end_date = match arg_matches.get_one::<SyrDate>("vs-end-date") {
+                    Some(vs_end_date) => **vs_end_date,
+                    None => vs_start_date.checked_add(length.days())?,
+                };
+                if **vs_start_date > vs_end_date {
+                    bail!("Start date of the span compared against is more recent than its end date");
+                }
+                SyrSpan::from_start_and_end(**vs_start_date, vs_end_date)
+            }
+            None => match against {
+                Baseline::Previous => SyrSpan::from_end_and_days_back(current.start.yesterday()?, length),
+                Baseline::LastYear => SyrSpan::from_start_and_end(current.start.checked_sub(1.year())?, current.end.checked_sub(1.year())?),
+            },
+        };
+        (current, baseline)
+    } else {
+        if arg_matches.contains_id("end-date") {
+            bail!("An end date requires a number of days back or a start date");
+        }
+        let period = arg_matches.get_one::<Period>("period").copied().unwrap_or(Period::Week);
+        let mut current = period.span_of(*today);
+        for _ in 0..arg_matches.get_one::<usize>("periods-back").copied().unwrap_or(0) {
+            current = period.span_of(current.start.yesterday()?.into());
+        }
+        let baseline = match against {
+            Baseline::Previous => period.span_of(current.start.yesterday()?.into()),
+            Baseline::LastYear => period.span_of(current.start.checked_sub(1.year())?.into()),
+        };
+        (current, baseline)
+    };
+
+    if arg_matches.get_flag("to-date") && current.end > **today {
+        let elapsed = (**today - current.start).get_days().max(0) as i64;
+        current.end = **today;
+        baseline.end = baseline.end.min(baseline.start.saturating_add(elapsed.days()));
+    }
+    Ok((current, baseline))
+}
+
+/// The relative change in percent, None when nothing was tracked over the baseline
+fn change(seconds: f64, baseline_seconds: f64) -> Option<f64> {
+    (baseline_seconds > 0.0).then(|| (seconds - baseline_seconds) / baseline_seconds * 100.0)
+}
+
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    let (current, baseline) = spans(arg_matches, today)?;
+
+    let seconds = |entry: &Entry, span: SyrSpan| span.into_iter().fold(0.0, |total, date| total + entry.get_bloc_duration(&date));
+    let mut comparisons = entries
+        .iter()
+        .map(|entry| {
+            let (seconds, baseline_seconds) = (seconds(entry, current), seconds(entry, baseline));
+            Record::new(entry, None, seconds).with_baseline(baseline_seconds, change(seconds, baseline_seconds))
+        })
+        .filter(|record| record.seconds > 0.0 || record.baseline_seconds > Some(0.0))
+        .collect_vec();
+    crate::output::sort(&mut comparisons);
+
+    let output = OutputFormat::from_arg_matches(arg_matches);
+    if !output.is_pretty() {
+        return crate::output::emit(output, &comparisons);
+    }
+    let baseline_of = |comparison: &Record| comparison.baseline_seconds.unwrap_or(0.0);
+
+    let title = format!(
+        "{} - {} vs {} - {}",
+        SyrDate::from(current.start),
+        SyrDate::from(current.end),
+        SyrDate::from(baseline.start),
+        SyrDate::from(baseline.end)
+    );
+    if comparisons.is_empty() {
+        println!(
+            "Nothing was tracked from {} to {} nor from {} to {}",
+            SyrDate::from(current.start),
+            SyrDate::from(current.end),
+            SyrDate::from(baseline.start),
+            SyrDate::from(baseline.end)
+        );
+        return Ok(());
+    }
+
+    let total = comparisons.iter().fold(0.0, |total, comparison| total + comparison.seconds);
+    let baseline_total = comparisons.iter().fold(0.0, |total, comparison| total + baseline_of(comparison));
+    let pad = comparisons
+        .iter()
+        .map(|comparison| comparison.name.len() + comparison.aliases.first().map(|alias| alias.len() + 2).unwrap_or(0))
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    // the totals are the longest durations printed
+    let width = total.max(baseline_total).s_str().len().max(f64::s_str_length());
+    let delta = |seconds: f64, baseline_seconds: f64| {
+        let delta = seconds - baseline_seconds;
+        let text = format!(
+            "{} {:>signed_width$}",
+            if delta > 0.0 {
+                "▲"
+            } else if delta < 0.0 {
+                "▼"
+            } else {
+                "="
+            },
+            format!("{}{}", if delta < 0.0 { "-" } else { "+" }, delta.abs().s_str()),
+            signed_width = width + 1
+        );
+        let change = match change(seconds, baseline_seconds) {
+            Some(change) => format!("{:>+8.1}%", change),
+            None => format!("{:>9}", "new"),
+        };
+        let line = format!("{} {}", text, change);
+        if delta > 0.0 {
+            line.green()
+        } else if delta < 0.0 {
+            line.red()
+        } else {
+            line.dim()
+        }
+    };
+
+    let header = format!(
+        "{:<pad$} {:>width$} {:>width$} {:>w$}",
+        "",
+        "Current",
+        "Baseline",
+        "Change",
+        w = width + 13
+    );
+    let dashes = format!("{:-<1$}", "", header.len().max(title.len()));
+    println!("{}\n{}", title.bold(), dashes.as_str().dim());
+    println!("{}", header.dim());
+    for comparison in comparisons.iter() {
+        let title = match comparison.aliases.first() {
+            Some(alias) => format!(
+                "{:<width$}",
+                format!("{}; {}", comparison.name, alias.as_str().dim()),
+                width = pad + 8
+            ),
+            None => format!("{:<pad$}", comparison.name),
+        };
+        println!(
+            "{} {:>width$} {:>width$} {}",
+            title,
+            comparison.seconds.s_str(),
+            baseline_of(comparison).s_str(),
+            delta(comparison.seconds, baseline_of(comparison))
+        );
+    }
+    println!("{}", dashes.as_str().dim());
+    println!(
+        "{:<pad$} {} {:>width$} {}",
+        "Total",
+        format!("{:>width$}", total.s_str()).bold(),
+        baseline_total.s_str(),
+        delta(total, baseline_total)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+
+    /// Returns the spans compared, today being Wednesday 14/10/2026 and weeks starting on Monday
+    fn spans_of(args: &[&str]) -> (SyrSpan, SyrSpan) {
+        let arg_matches = subcommand()
+            .try_get_matches_from(std::iter::once("compare").chain(args.iter().copied()))
+            .unwrap();
+        spans(&arg_matches, &SyrDate::from(date(2026, 10, 14))).unwrap()
+    }
+
+    fn span(start: jiff::civil::Date, end: jiff::civil::Date) -> SyrSpan {
+        SyrSpan::from_start_and_end(start, end)
+    }
+
+    #[test]
+    fn previous_week() {
+        let (current, baseline) = spans_of(&[]);
+        assert_eq!(current, span(date(2026, 10, 12), date(2026, 10, 18)));
+        assert_eq!(baseline, span(date(2026, 10, 5), date(2026, 10, 11)));
+    }
+
+    #[test]
+    fn to_date() {
+        let (current, baseline) = spans_of(&["--to-date"]);
+        assert_eq!(current, span(date(2026, 10, 12), date(2026, 10, 14)));
+        assert_eq!(baseline, span(date(2026, 10, 5), date(2026, 10, 7)));
+        // past periods are not truncated
+        let (current, _) = spans_of(&["--to-date", "--back", "1"]);
+        assert_eq!(current, span(date(2026, 10, 5), date(2026, 10, 11)));
+    }
+
+    #[test]
+    fn last_year_week() {
+        let (current, baseline) = spans_of(&["--against", "last-year"]);
+        assert_eq!(current, span(date(2026, 10, 12), date(2026, 10, 18)));
+        // the week containing 12/10/2025, a Sunday
+        assert_eq!(baseline, span(date(2025, 10, 6), date(2025, 10, 12)));
+    }
+
+    #[test]
+    fn custom_spans() {
+        let (current, baseline) = spans_of(&["--start", "2026-10-10"]);
+        assert_eq!(current, span(date(2026, 10, 10), date(2026, 10, 14)));
+        assert_eq!(baseline, span(date(2026, 10, 5), date(2026, 10, 9)));
+
+        let (_, baseline) = spans_of(&["--start", "2026-10-10", "--end", "2026-10-11", "--against", "last-year"]);
+        assert_eq!(baseline, span(date(2025, 10, 10), date(2025, 10, 11)));
+
+        let (_, baseline) = spans_of(&["--start", "2026-10-10", "--vs-start", "2026-01-01"]);
+        assert_eq!(baseline, span(date(2026, 1, 1), date(2026, 1, 5)));
+    }
+}
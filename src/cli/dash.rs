@@ -0,0 +1,228 @@
+use super::*;
+use crate::{
+    picker::Picker,
+    utils::{enter_fullscreen_mode, exit_fullscreen_mode, horizontal_bar},
+};
+use crossterm::{cursor, queue, style::Print, terminal};
+
+pub(super) fn subcommand() -> Command {
+    Command::new("dash")
+        .aliases(["dashboard", "tui"])
+        .about("Open a live dashboard of the time tracked today and this week")
+        .long_about(
+            "This subcommand is used to open a full-screen dashboard showing the running stopwatch or check-in, the time tracked today and this week\nEntries can be started, stopped, checked-in and checked-out from within the dashboard\naliases: 'dashboard', 'tui'",
+        )
+}
+
+pub fn process(_arg_matches: &ArgMatches, entries: Entries, today: &SyrDate) -> Result<()> {
+    let mut dashboard = Dashboard {
+        entries,
+        today,
+        week: SyrSpan::week_of(**today)?.into_iter().collect(),
+        stopwatch: None,
+        check_in: CheckIn::find()?,
+        status: None,
+    };
+    let frame_period = Duration::from_millis(config::Config::get().frame_period);
+    let mut stdout = std::io::stdout();
+
+    enter_fullscreen_mode();
+    let result = loop {
+        dashboard.tick();
+        if let Err(err) = dashboard.draw(&mut stdout) {
+            break Err(err.into());
+        }
+        let key = match event::poll(frame_period).and_then(|ready| if ready { event::read().map(Some) } else { Ok(None) }) {
+            Ok(Some(event::Event::Key(key))) if key.kind == event::KeyEventKind::Press => key,
+            Ok(_) => continue,
+            Err(err) => break Err(err.into()),
+        };
+        // raw mode swallows SIGINT, so Ctrl-C is handled as a key
+        let ctrl = key.modifiers.contains(event::KeyModifiers::CONTROL);
+        let outcome = match key.code {
+            event::KeyCode::Char('c') if ctrl => break Ok(()),
+            event::KeyCode::Char('q') | event::KeyCode::Char('Q') | event::KeyCode::Esc => break Ok(()),
+            event::KeyCode::Char('s') | event::KeyCode::Char('S') => dashboard.start(),
+            event::KeyCode::Char('x') | event::KeyCode::Char('X') | event::KeyCode::Char(' ') => dashboard.stop(),
+            event::KeyCode::Char('c') | event::KeyCode::Char('C') => dashboard.toggle_check_in(),
+            _ => Ok(()),
+        };
+        dashboard.status = outcome.err().map(|err| err.to_string());
+    };
+    exit_fullscreen_mode();
+
+    result.and(dashboard.stop())
+}
+
+struct Stopwatch {
+    name: String,
+    start: Instant,
+    instant: Instant,
+    autosave_instant: Instant,
+}
+
+struct Dashboard<'a> {
+    entries: Entries,
+    today: &'a SyrDate,
+    week: Vec<SyrDate>,
+    stopwatch: Option<Stopwatch>,
+    check_in: Option<CheckIn>,
+    status: Option<String>,
+}
+
+impl Dashboard<'_> {
+    fn entry_mut(&mut self, name: &str) -> Result<&mut Entry> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.name == name)
+            .ok_or_eyre("Failed to find an entry that matches the name")
+    }
+
+    /// Adds the time elapsed since the last tick to the running entry, autosaving when needed
+    fn tick(&mut self) {
+        let Some(mut stopwatch) = self.stopwatch.take() else {
+            return;
+        };
+        let today = self.today;
+        let new_instant = Instant::now();
+        if let Ok(entry) = self.entry_mut(&stopwatch.name) {
+            entry.increase_bloc_duration(today, new_instant.duration_since(stopwatch.instant).as_secs_f64());
+            if new_instant.duration_since(stopwatch.autosave_instant) > Duration::from_secs(config::Config::get().autosave_period as u64) {
+                if let Err(error) = entry.save() {
+                    self.status = Some(format!("Warning: Failed to autosave progress, '{}'", error));
+                }
+                stopwatch.autosave_instant = new_instant;
+            }
+        }
+        stopwatch.instant = new_instant;
+        self.stopwatch = Some(stopwatch);
+    }
+
+    fn pick(&self) -> Option<String> {
+        let candidates = self.entries.iter().filter(|entry| entry.indexed).collect_vec();
+        Picker::new(candidates, "", self.today).run().map(|entry| entry.name)
+    }
+
+    fn start(&mut self) -> Result<()> {
+        let Some(name) = self.pick() else {
+            return Ok(());
+        };
+        self.stop()?;
+        let now = Instant::now();
+        self.stopwatch = Some(Stopwatch {
+            name,
+            start: now,
+            instant: now,
+            autosave_instant: now,
+        });
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.tick();
+        if let Some(stopwatch) = self.stopwatch.take() {
+            self.entry_mut(&stopwatch.name)?.save()?;
+            Sessions::record(
+                &stopwatch.name,
+                self.today,
+                stopwatch.instant.duration_since(stopwatch.start).as_secs_f64(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn toggle_check_in(&mut self) -> Result<()> {
+        if let Some(check_in) = self.check_in.take() {
+            let today = self.today;
+            let entry = self.entry_mut(&check_in.name)?;
+            check_in.complete(entry, today)?;
+        } else if let Some(name) = self.pick() {
+            let entry = self.entry_mut(&name)?;
+            self.check_in = Some(CheckIn::create(entry)?);
+        }
+        Ok(())
+    }
+
+    fn draw(&self, stdout: &mut std::io::Stdout) -> std::io::Result<()> {
+        // some pseudo-terminals report a size of zero
+        let columns = match terminal::size()?.0 {
+            0 => 80,
+            columns => columns as usize,
+        };
+        let mut lines: Vec<String> = Vec::new();
+
+        let weekday = self.today.weekday().to_string();
+        lines.push((weekday + " - " + self.today.to_string().as_str()).bold().to_string());
+        lines.push(format!("{:-<1$}", "", columns.min(60)).dim().to_string());
+
+        if let Some(stopwatch) = self.stopwatch.as_ref() {
+            let session = stopwatch.instant.duration_since(stopwatch.start).as_secs_f64();
+            lines.push(format!(
+                "{} {} {}",
+                ARROW.green(),
+                stopwatch.name.as_str().bold(),
+                session.live_str()
+            ));
+        } else if let Some(check_in) = self.check_in.as_ref() {
+            let elapsed = check_in.elapsed().unwrap_or_default();
+            lines.push(format!(
+                "{} {} {}",
+                ARROW.yellow(),
+                check_in.name.as_str().bold(),
+                elapsed.live_str()
+            ));
+        } else {
+            lines.push("Nothing running".dim().to_string());
+        }
+        lines.push(String::new());
+
+        // today
+        let mut bones = self
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry.print_name_and_first_alias(), entry.get_block_duration_opt(self.today)?)))
+            .collect_vec();
+        bones.sort_by(|a, b| config::Config::get().sort_option.compare((&a.0, a.1), (&b.0, b.1)));
+        let pad = bones.iter().map(|(title, _)| title.chars().count()).max().unwrap_or(0);
+        lines.push("Today".bold().to_string());
+        for (title, duration) in bones.iter() {
+            lines.push(format!("{:<pad$} : {}", title, duration.s_str()));
+        }
+        let total: f64 = bones.iter().map(|(_, duration)| duration).sum();
+        lines.push(format!("{} {}", ARROWHEAD.dark_green(), total.s_str()));
+        lines.push(String::new());
+
+        // week
+        let daily_totals = self
+            .week
+            .iter()
+            .map(|date| (date, self.entries.iter().map(|entry| entry.get_bloc_duration(date)).sum::<f64>()))
+            .collect_vec();
+        let max = daily_totals.iter().map(|(_, total)| *total).fold(0.0, f64::max);
+        let width = columns.saturating_sub(4 + f64::s_str_length() + 2).min(60);
+        lines.push("Week".bold().to_string());
+        for (date, total) in daily_totals.iter() {
+            let label: String = date.weekday().to_string().chars().take(3).collect();
+            let bar = horizontal_bar(if max > 0.0 { total / max } else { 0.0 }, width);
+            if *date == self.today {
+                lines.push(format!("{} {} {}", label.bold(), bar.green(), total.s_str().bold()));
+            } else {
+                lines.push(format!("{} {} {}", label, bar.dark_green(), total.s_str()));
+            }
+        }
+        let weekly_total: f64 = daily_totals.iter().map(|(_, total)| total).sum();
+        lines.push(format!("{} {}", ARROW.green(), weekly_total.s_str().bold()));
+        lines.push(String::new());
+
+        lines.push("[s] start  [x] stop  [c] check-in/out  [q] quit".dim().to_string());
+        if let Some(status) = self.status.as_ref() {
+            lines.push(status.as_str().red().to_string());
+        }
+
+        queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+        for (row, line) in lines.into_iter().enumerate() {
+            queue!(stdout, cursor::MoveTo(0, row as u16), Print(line))?;
+        }
+        stdout.flush()
+    }
+}
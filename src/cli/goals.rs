@@ -0,0 +1,109 @@
+use super::*;
+use crate::{
+    data::goal::{Progress, goals_of},
+    utils::horizontal_bar,
+};
+
+/// The width of progress bars, in characters
+const BAR_WIDTH: usize = 20;
+
+pub(super) fn subcommand() -> Command {
+    Command::new("goals")
+        .aliases(["targets", "goal"])
+        .about("Report which goals were met over a span of dates")
+        .long_about("This subcommand is used to list every day, week or month of the configured goals within a span of dates, along with whether the goal was met\nthe past four weeks are used when no span is specified\naliases: 'targets', 'goal'")
+        .args(span_args())
+}
+
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    let date_span = match date_span(arg_matches, today)? {
+        Some(date_span) => date_span,
+        None => SyrSpan::from_end_and_days_back(**arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today), 27),
+    };
+
+    if config::Config::get().goals.is_empty() {
+        bail!("No goals are set, goals are added to the 'goals' of the configuration file");
+    }
+    let goals = goals_of(&config::Config::get().goals, entries)
+        .into_iter()
+        .map(|(goal, entry)| (goal, entry, goal.history(entry, date_span)))
+        .collect_vec();
+
+    let output = OutputFormat::from_arg_matches(arg_matches);
+    if !output.is_pretty() {
+        let records = goals
+            .iter()
+            .flat_map(|(_, entry, history)| {
+                history
+                    .iter()
+                    .map(|progress| Record::new(entry, Some(&progress.period.start), progress.seconds).with_target(progress.target))
+            })
+            .collect_vec();
+        return crate::output::emit(output, &records);
+    }
+
+    let (mut total_met, mut total_due) = (0, 0);
+    for (goal, entry, history) in goals {
+        let title = format!("{} - {}", entry.name, goal);
+        let pad = history.iter().map(|progress| progress.period.label.len()).max().unwrap_or(0);
+        let dashes = format!("{:-<1$}", "", usize::max(pad + 3 + f64::s_str_length() + 20, title.len()));
+        println!("{}\n{}", title.bold(), dashes.as_str().dim());
+
+        let (mut met, mut due) = (0, 0);
+        for progress in history.iter() {
+            let status = if progress.is_met() {
+                met += 1;
+                due += 1;
+                "met".to_string().green()
+            } else if progress.period.end >= *today {
+                // the period is not over yet
+                format!("{} left", progress.remaining().s_str()).yellow()
+            } else {
+                due += 1;
+                format!("missed by {}", progress.remaining().s_str()).red()
+            };
+            println!("{:<pad$} : {}  {}", progress.period.label, progress.seconds.s_str(), status);
+        }
+        println!("{} met {} of {}\n", ARROWHEAD.dark_green(), met, due);
+        total_met += met;
+        total_due += due;
+    }
+
+    println!("{} {}", ARROW.green(), format!("{} of {} goals met", total_met, total_due).bold());
+    Ok(())
+}
+
+/// Prints the progress of every goal over its day, week or month containing the date, nothing if no goal is set, daily goals being labelled with the date if need be
+pub(super) fn print_progress(entries: &Entries, date: SyrDate, label_days: bool) {
+    let goals = goals_of(&config::Config::get().goals, entries)
+        .into_iter()
+        .map(|(goal, entry)| {
+            let title = match goal.per {
+                Granularity::Day if label_days => format!("{} on {}", goal, date),
+                _ => goal.to_string(),
+            };
+            (entry.name.as_str(), title, goal.progress(entry, date))
+        })
+        .collect_vec();
+    if goals.is_empty() {
+        return;
+    }
+    let name_pad = goals.iter().map(|(name, _, _)| name.len()).max().unwrap_or(0);
+    let goal_pad = goals.iter().map(|(_, goal, _)| goal.len()).max().unwrap_or(0);
+
+    println!("\n{}", "Goals".bold());
+    for (name, goal, progress) in goals {
+        println!("{:<name_pad$}  {:<goal_pad$}  {}", name, goal, progress_bar(&progress));
+    }
+}
+
+/// Returns the bar, percentage and time left of the progress
+fn progress_bar(progress: &Progress) -> String {
+    let bar = horizontal_bar(progress.fraction(), BAR_WIDTH);
+    let percent = format!("{:>3.0}%", progress.fraction() * 100.0);
+    if progress.is_met() {
+        format!("{} {}  {}", bar.green(), percent, "met".green())
+    } else {
+        format!("{} {}  {} left", bar.yellow(), percent, progress.remaining().s_str())
+    }
+}
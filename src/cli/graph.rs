@@ -1,62 +1,158 @@
 use super::*;
+use crate::data::graphing::{GraphFormat, GraphOptions, GraphStyle, OverlayOptions};
 
 pub(super) fn subcommand() -> Command {
     Command::new("graph")
         .aliases(["plot", "draw"])
         .about("Graph the time tracked by entries in a given timeframe")
         .long_about("This subcommand is used to graph the time tracked by entries in the provided timeframe\naliases: 'plot', 'draw'")
+        .args(span_args())
         .arg(
-            Arg::new("days-back")
-                .help("The number of days back")
-                .long("The number of days back, if an end-date is not specified then today will be used")
-                .short('d')
-                .long("days")
-                .alias("days-back")
+            Arg::new("format")
+                .help("The file format of the graph")
+                .short('f')
+                .long("format")
+                .value_parser(value_parser!(GraphFormat))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("granularity")
+                .help("Plot one point per day, week or month")
+                .short('g')
+                .long("granularity")
+                .value_parser(value_parser!(Granularity))
+                .default_value("day")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("moving-average")
+                .help("Overlay a moving average over the given number of points, the configured window by default")
+                .short('m')
+                .long("moving-average")
+                .alias("ma")
+                .num_args(0..=1)
                 .value_parser(value_parser!(usize))
-                .action(ArgAction::Set)
-                .group("days-back-group"),
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("trend")
+                .help("Overlay a least-squares trend line")
+                .long("trend")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("overlay")
+                .help("The entry/entries that also get the moving average and trend line, the total always does")
+                .long("overlay")
+                .num_args(1..)
+                .requires("overlays")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("style")
+                .help("How entries are drawn on the graph")
+                .long("style")
+                .value_parser(value_parser!(GraphStyle))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("width")
+                .help("The width of the graph in pixels at 96 DPI")
+                .long("width")
+                .value_parser(value_parser!(u32))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("height")
+                .help("The height of the graph in pixels at 96 DPI")
+                .long("height")
+                .value_parser(value_parser!(u32))
+                .action(ArgAction::Set),
         )
         .arg(
-            Arg::new("start-date")
-                .help("The start date")
-                .long_help("The start date, if an end-date is not specified then today will be used")
-                .short('s')
-                .long("start")
-                .alias("start-date")
-                .action(ArgAction::Set)
-                .value_parser(clap::value_parser!(SyrDate)),
+            Arg::new("dpi")
+                .help("The resolution of the graph, every dimension is scaled by dpi / 96")
+                .long("dpi")
+                .value_parser(value_parser!(u32).range(1..))
+                .action(ArgAction::Set),
         )
         .arg(
-            Arg::new("end-date")
-                .help("The end date")
-                .short('e')
-                .long("end")
-                .alias("end-date")
-                .action(ArgAction::Set)
-                .value_parser(clap::value_parser!(SyrDate)),
+            Arg::new("theme")
+                .help("The name of the theme, a built-in one or one defined in the configuration")
+                .long("theme")
+                .action(ArgAction::Set),
         )
-        .group(ArgGroup::new("days-back-group").conflicts_with("start-date"))
+        .arg(
+            Arg::new("terminal")
+                .help("Draw the graph in the terminal instead of saving it as an image")
+                .short('t')
+                .long("terminal")
+                .conflicts_with_all(["format", "style", "width", "height", "dpi"])
+                .action(ArgAction::SetTrue),
+        )
+        .group(ArgGroup::new("overlays").args(["moving-average", "trend"]).multiple(true))
 }
 
 pub fn process(arg_matches: &ArgMatches, entries: Entries, today: &SyrDate) -> Result<()> {
-    let date_span: SyrSpan = {
-        // days-back + specified end-date or not
-        if let Some(num) = arg_matches.get_one::<usize>("days-back") {
-            let end_date = *arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today);
-            SyrSpan::from_end_and_days_back(*end_date, *num as i64)
-        }
-        // start-date + specified end-date or not
-        else if let Some(start_date) = arg_matches.get_one::<SyrDate>("start-date") {
-            let end_date = *arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today);
-
-            if *start_date > end_date {
-                bail!("Start date is more recent than end date");
+    let date_span = date_span(arg_matches, today)?.ok_or_eyre("A number of days back or a start date is required")?;
+
+    let granularity = *arg_matches
+        .get_one::<Granularity>("granularity")
+        .ok_or_eyre("Invalid subcommand usage")?;
+
+    let overlays = OverlayOptions {
+        moving_average: arg_matches.contains_id("moving-average").then(|| {
+            arg_matches
+                .get_one::<usize>("moving-average")
+                .copied()
+                .unwrap_or(config::Config::get().graph_moving_average_window)
+        }),
+        trend: arg_matches.get_flag("trend"),
+        entries: match arg_matches.get_many::<String>("overlay") {
+            Some(queries) => {
+                let selection_mode = selection_mode(arg_matches);
+                queries
+                    .map(|query| entries.choose(&query.to_uppercase(), IndexOptions::All, selection_mode, today))
+                    .flatten_ok()
+                    .map_ok(|entry| entry.name)
+                    .collect::<Result<_>>()?
             }
-            SyrSpan::from_start_and_end(**start_date, *end_date)
-        } else {
-            bail!("Invalid subcommand usage");
-        }
+            None => Vec::new(),
+        },
     };
 
-    crate::data::graphing::graph(entries, date_span)
+    let theme = arg_matches.get_one::<String>("theme").cloned();
+
+    if arg_matches.get_flag("terminal") {
+        crate::data::graphing::graph_terminal(entries, date_span, granularity, &overlays, theme.as_deref())
+    } else {
+        let options = GraphOptions {
+            format: arg_matches.get_one::<GraphFormat>("format").copied(),
+            width: arg_matches.get_one::<u32>("width").copied(),
+            height: arg_matches.get_one::<u32>("height").copied(),
+            dpi: arg_matches.get_one::<u32>("dpi").copied(),
+            style: arg_matches.get_one::<GraphStyle>("style").copied(),
+            theme,
+            overlays,
+        };
+        crate::data::graphing::graph(entries, date_span, granularity, &options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_requires_an_overlay() {
+        let matches = |args: &[&str]| {
+            subcommand()
+                .try_get_matches_from(["graph", "--days", "7"].iter().chain(args))
+                .is_ok()
+        };
+        assert!(!matches(&["--overlay", "gym"]));
+        assert!(matches(&["--overlay", "gym", "--trend"]));
+        assert!(matches(&["--overlay", "gym", "--moving-average"]));
+        assert!(matches(&["--trend", "--moving-average", "3"]));
+    }
 }
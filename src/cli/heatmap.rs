@@ -0,0 +1,82 @@
+use super::*;
+use crate::data::graphing::{GraphFormat, GraphOptions};
+
+pub(super) fn subcommand() -> Command {
+    Command::new("heatmap")
+        .aliases(["calendar", "heat"])
+        .about("Draw a calendar heatmap of the time tracked each day of a year")
+        .long_about("This subcommand is used to draw a calendar heatmap with one cell per day, colored by the time tracked on that day\naliases: 'calendar', 'heat'")
+        .arg(
+            Arg::new("year")
+                .help("The year, defaults to the current one")
+                .short('y')
+                .long("year")
+                .value_parser(value_parser!(i16))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("entry")
+                .help("Only count the time tracked by this entry")
+                .short('e')
+                .long("entry")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("format")
+                .help("The file format of the heatmap")
+                .short('f')
+                .long("format")
+                .value_parser(value_parser!(GraphFormat))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("dpi")
+                .help("The resolution of the heatmap, every dimension is scaled by dpi / 96")
+                .long("dpi")
+                .value_parser(value_parser!(u32).range(1..))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("theme")
+                .help("The name of the theme, a built-in one or one defined in the configuration")
+                .long("theme")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("terminal")
+                .help("Draw the heatmap in the terminal instead of saving it as an image")
+                .short('t')
+                .long("terminal")
+                .conflicts_with_all(["format", "dpi"])
+                .action(ArgAction::SetTrue),
+        )
+}
+
+pub fn process(arg_matches: &ArgMatches, entries: Entries, today: &SyrDate) -> Result<()> {
+    let year = arg_matches.get_one::<i16>("year").copied().unwrap_or(today.year());
+
+    let (entries, subject) = match arg_matches.get_one::<String>("entry") {
+        Some(query) => match entries.choose(&query.to_uppercase(), IndexOptions::All, selection_mode(arg_matches), today)? {
+            Some(entry) => {
+                let name = entry.name.clone();
+                (Entries::from(vec![entry]), Some(name))
+            }
+            None => return Ok(()),
+        },
+        None => (entries, None),
+    };
+
+    let theme = arg_matches.get_one::<String>("theme").cloned();
+
+    if arg_matches.get_flag("terminal") {
+        crate::data::graphing::heatmap_terminal(entries, year, subject.as_deref(), theme.as_deref())
+    } else {
+        let options = GraphOptions {
+            format: arg_matches.get_one::<GraphFormat>("format").copied(),
+            dpi: arg_matches.get_one::<u32>("dpi").copied(),
+            theme,
+            ..Default::default()
+        };
+        crate::data::graphing::heatmap(entries, year, subject.as_deref(), &options)
+    }
+}
@@ -0,0 +1,59 @@
+use super::*;
+use crate::data::invoice::{Client, Invoice, InvoiceFormat};
+
+pub(super) fn subcommand() -> Command {
+    Command::new("invoice")
+        .aliases(["bill"])
+        .about("Make out an invoice of the time tracked for a client")
+        .long_about("This subcommand is used to make out an invoice of the billable time tracked on the entries of a configured client, with one line per entry, or per entry per day\nthe current month is used when no span is specified\naliases: 'bill'")
+        .arg(
+            Arg::new("client")
+                .help("The name of the client, as configured")
+                .short('c')
+                .long("client")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .args(span_args())
+        .arg(
+            Arg::new("per-day")
+                .help("List the time tracked on each entry every day instead of over the whole span")
+                .long("per-day")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("format")
+                .help("The format the invoice is written in")
+                .short('f')
+                .long("format")
+                .value_parser(value_parser!(InvoiceFormat))
+                .action(ArgAction::Set),
+        )
+        .args(sum::rounding_args())
+}
+
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    if !OutputFormat::from_arg_matches(arg_matches).is_pretty() {
+        bail!("Invoices are not affected by --output, their format is chosen with --format");
+    }
+    let date_span = match date_span(arg_matches, today)? {
+        Some(date_span) => date_span,
+        None => {
+            let end_date = arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today);
+            SyrSpan::from_start_and_end(end_date.first_of_month(), **end_date)
+        }
+    };
+
+    let name = arg_matches.get_one::<String>("client").map(String::as_str).unwrap_or_default();
+    let client = Client::named(&config::Config::get().clients, name)?;
+    let invoice = Invoice::new(
+        client,
+        entries,
+        date_span,
+        sum::rounding(arg_matches),
+        arg_matches.get_flag("per-day"),
+    )?;
+    let format = arg_matches.get_one::<InvoiceFormat>("format").copied().unwrap_or_default();
+    print!("{}", invoice.render(format));
+    Ok(())
+}
@@ -41,6 +41,22 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries) -> Result<()> {
         (false, true) => entries.iter().filter(|entry| !entry.indexed).collect(),
     };
 
+    let output = OutputFormat::from_arg_matches(arg_matches);
+    if !output.is_pretty() {
+        let records = if arg_matches.get_flag("extra") {
+            entries
+                .iter()
+                .flat_map(|entry| entry.blocs.iter().map(|(date, duration)| Record::new(entry, Some(date), *duration)))
+                .collect_vec()
+        } else {
+            entries
+                .iter()
+                .map(|entry| Record::new(entry, None, entry.blocs.values().fold(0.0, |acc, x| acc + x)))
+                .collect_vec()
+        };
+        return crate::output::emit(output, &records);
+    }
+
     if arg_matches.get_flag("extra") {
         for entry in entries.iter() {
             println!("• {:?}", entry)
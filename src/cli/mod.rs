@@ -3,28 +3,38 @@ mod add;
 mod backup;
 mod check_in;
 mod check_out;
+mod compare;
+mod dash;
 mod gen_completions;
+mod goals;
 mod graph;
+mod heatmap;
+mod invoice;
 mod list;
+mod month;
 mod prune;
 mod reindex;
 mod remove;
 mod start;
+mod stats;
 mod sum;
 mod today;
 mod unindex;
 mod update_add;
 mod update_sub;
 mod week;
+mod year;
 
 // Imports
 use crate::{
     animation, config,
     data::{
-        Entries, Entry, IndexOptions,
-        syrtime::{SyrDate, SyrSpan, TimeFormatting, WeekdayFormatting},
+        CheckIn, Entries, Entry, IndexOptions, SelectionMode,
+        billing::{Rounding, RoundingMode, RoundingUnit},
+        sessions::Sessions,
+        syrtime::{Bucket, Granularity, SyrDate, SyrSpan, TIME_FORMAT, TimeFormat, TimeFormatting, WeekdayFormatting, parse_duration},
     },
-    dirs::Dirs,
+    output::{OutputFormat, Record},
     utils::{ARROW, ARROWHEAD, enter_clean_input_mode, exit_clean_input_mode},
 };
 use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command, value_parser};
@@ -34,34 +44,75 @@ use color_eyre::eyre::{Context, OptionExt, bail};
 use crossterm::{event, style::Stylize};
 use itertools::Itertools;
 use jiff::ToSpan;
-use jiff::civil::{DateTime, Weekday};
+use jiff::civil::DateTime;
 use serde::{Deserialize, Serialize};
 use std::{
-    io::{Read, Write},
+    io::{IsTerminal, Write},
     path::PathBuf,
     time::{Duration, Instant},
 };
 
 pub fn build_cli() -> Command {
-    Command::new("syr").subcommands([
-        add::subcommand(),
-        list::subcommand(),
-        remove::subcommand(),
-        start::subcommand(),
-        update_add::subcommand(),
-        update_sub::subcommand(),
-        today::subcommand(),
-        backup::subcommand(),
-        unindex::subcommand(),
-        reindex::subcommand(),
-        sum::subcommand(),
-        prune::subcommand(),
-        graph::subcommand(),
-        check_in::subcommand(),
-        check_out::subcommand(),
-        week::subcommand(),
-        gen_completions::subcommand(),
-    ])
+    Command::new("syr")
+        .arg(
+            Arg::new("output")
+                .help("The output format used by reporting commands")
+                .long("output")
+                .global(true)
+                .value_parser(value_parser!(OutputFormat))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("time-format")
+                .help("The format durations are displayed in, overriding the configuration")
+                .long("time-format")
+                .global(true)
+                .value_parser(value_parser!(TimeFormat))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("exact")
+                .help("Only select entries whose name or alias exactly matches the query, fail otherwise")
+                .long("exact")
+                .global(true)
+                .conflicts_with("yes")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("yes")
+                .help("Automatically select the best matching entry, default when stdin is not a terminal")
+                .long("yes")
+                .alias("best")
+                .global(true)
+                .action(ArgAction::SetTrue),
+        )
+        .subcommands([
+            add::subcommand(),
+            list::subcommand(),
+            remove::subcommand(),
+            start::subcommand(),
+            update_add::subcommand(),
+            update_sub::subcommand(),
+            today::subcommand(),
+            backup::subcommand(),
+            unindex::subcommand(),
+            reindex::subcommand(),
+            sum::subcommand(),
+            prune::subcommand(),
+            graph::subcommand(),
+            heatmap::subcommand(),
+            check_in::subcommand(),
+            check_out::subcommand(),
+            week::subcommand(),
+            month::subcommand(),
+            year::subcommand(),
+            dash::subcommand(),
+            goals::subcommand(),
+            stats::subcommand(),
+            compare::subcommand(),
+            invoice::subcommand(),
+            gen_completions::subcommand(),
+        ])
 }
 
 pub fn cli(entries: Entries, today: SyrDate, dt: DateTime) -> Result<()> {
@@ -69,38 +120,143 @@ pub fn cli(entries: Entries, today: SyrDate, dt: DateTime) -> Result<()> {
 
     let arg_matches = command.get_matches();
 
+    if let Some(time_format) = arg_matches
+        .subcommand()
+        .and_then(|(_, arg_matches)| arg_matches.get_one::<TimeFormat>("time-format"))
+    {
+        TIME_FORMAT
+            .set(*time_format)
+            .map_err(|_| color_eyre::eyre::eyre!("Failed to lock the time format"))?;
+    }
+
+    // output redirected to a file is left as it is
+    if OutputFormat::from_arg_matches(&arg_matches).is_pretty() && std::io::stdout().is_terminal() {
+        println!();
+    }
+
     match arg_matches.subcommand() {
         Some(("add", arg_matches)) => add::process(arg_matches, &entries),
         Some(("list", arg_matches)) => list::process(arg_matches, &entries),
-        Some(("remove", arg_matches)) => remove::process(arg_matches, &entries),
+        Some(("remove", arg_matches)) => remove::process(arg_matches, &entries, &today),
         Some(("start", arg_matches)) => start::process(arg_matches, &entries, &today),
         Some(("update-add", arg_matches)) => update_add::process(arg_matches, &entries, &today),
         Some(("update-sub", arg_matches)) => update_sub::process(arg_matches, &entries, &today),
         Some(("today", arg_matches)) => today::process(arg_matches, &entries, &today),
         Some(("backup", arg_matches)) => backup::process(arg_matches, &entries, &dt),
-        Some(("unindex", arg_matches)) => unindex::process(arg_matches, &entries),
-        Some(("reindex", arg_matches)) => reindex::process(arg_matches, &entries),
+        Some(("unindex", arg_matches)) => unindex::process(arg_matches, &entries, &today),
+        Some(("reindex", arg_matches)) => reindex::process(arg_matches, &entries, &today),
         Some(("sum", arg_matches)) => sum::process(arg_matches, &entries, &today),
         Some(("prune", arg_matches)) => prune::process(arg_matches, entries),
         Some(("graph", arg_matches)) => graph::process(arg_matches, entries, &today),
-        Some(("check-in", arg_matches)) => check_in::process(arg_matches, &entries),
+        Some(("heatmap", arg_matches)) => heatmap::process(arg_matches, entries, &today),
+        Some(("check-in", arg_matches)) => check_in::process(arg_matches, &entries, &today),
         Some(("check-out", arg_matches)) => check_out::process(arg_matches, &entries, &today),
         Some(("week", arg_matches)) => week::process(arg_matches, &entries, &today),
+        Some(("month", arg_matches)) => month::process(arg_matches, &entries, &today),
+        Some(("year", arg_matches)) => year::process(arg_matches, &entries, &today),
+        Some(("dash", arg_matches)) => dash::process(arg_matches, entries, &today),
+        Some(("goals", arg_matches)) => goals::process(arg_matches, &entries, &today),
+        Some(("stats", arg_matches)) => stats::process(arg_matches, &entries, &today),
+        Some(("compare", arg_matches)) => compare::process(arg_matches, &entries, &today),
+        Some(("invoice", arg_matches)) => invoice::process(arg_matches, &entries, &today),
         Some(("gen-completions", arg_matches)) => gen_completions::process(arg_matches),
         _ => Ok(()),
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// Determines how entries are selected, scripts and pipes are never prompted
+fn selection_mode(arg_matches: &ArgMatches) -> SelectionMode {
+    if arg_matches.get_flag("exact") {
+        SelectionMode::Exact
+    } else if arg_matches.get_flag("yes") || !std::io::stdin().is_terminal() {
+        SelectionMode::Best
+    } else {
+        SelectionMode::Interactive
+    }
+}
+
+/// The days-back, start-date and end-date arguments of the subcommands reporting over a span of dates
+fn span_args() -> [Arg; 3] {
+    [
+        Arg::new("days-back")
+            .help("The number of days back included, up to the end date")
+            .short('d')
+            .long("days")
+            .alias("days-back")
+            .value_parser(value_parser!(usize))
+            .conflicts_with("start-date")
+            .action(ArgAction::Set),
+        Arg::new("start-date")
+            .help("The start date")
+            .short('s')
+            .long("start")
+            .alias("start-date")
+            .allow_hyphen_values(true)
+            .value_parser(value_parser!(SyrDate))
+            .action(ArgAction::Set),
+        Arg::new("end-date")
+            .help("The end date, today by default")
+            .short('e')
+            .short_alias('l')
+            .long("end")
+            .alias("end-date")
+            .allow_hyphen_values(true)
+            .value_parser(value_parser!(SyrDate))
+            .action(ArgAction::Set),
+    ]
+}
+
+/// Returns the span given by the span arguments, None if neither days back nor a start date is given
+fn date_span(arg_matches: &ArgMatches, today: &SyrDate) -> Result<Option<SyrSpan>> {
+    let end_date = *arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today);
+    if let Some(num) = arg_matches.get_one::<usize>("days-back") {
+        Ok(Some(SyrSpan::from_end_and_days_back(*end_date, *num as i64)))
+    } else if let Some(start_date) = arg_matches.get_one::<SyrDate>("start-date") {
+        if *start_date > end_date {
+            bail!("Start date is more recent than end date");
+        }
+        Ok(Some(SyrSpan::from_start_and_end(**start_date, *end_date)))
+    } else {
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub(crate) enum SortOptions {
     NameAscending,
     NameDescending,
     DurationAscending,
+    #[default]
     DurationDescending,
 }
 
-impl Default for SortOptions {
-    fn default() -> Self {
-        Self::DurationDescending
+impl SortOptions {
+    /// Compares two (name, duration) pairs according to the sort option
+    pub(crate) fn compare(&self, a: (&str, f64), b: (&str, f64)) -> std::cmp::Ordering {
+        match self {
+            Self::NameAscending => a.0.cmp(b.0),
+            Self::NameDescending => b.0.cmp(a.0),
+            Self::DurationAscending => a.1.total_cmp(&b.1),
+            Self::DurationDescending => b.1.total_cmp(&a.1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_may_start_with_a_hyphen() {
+        for (args, id) in [
+            (["syr", "sum", "--start", "-3d"], "start-date"),
+            (["syr", "graph", "-s", "-2w"], "start-date"),
+            (["syr", "compare", "--start", "-1m"], "start-date"),
+            (["syr", "goals", "--end", "-1d"], "end-date"),
+        ] {
+            let arg_matches = build_cli().try_get_matches_from(args).unwrap();
+            let (_, sub_matches) = arg_matches.subcommand().unwrap();
+            assert!(sub_matches.get_one::<SyrDate>(id).is_some());
+        }
     }
 }
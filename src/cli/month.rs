@@ -0,0 +1,137 @@
+use super::*;
+use crate::data::syrtime::MONTH_NAMES;
+
+pub(super) fn subcommand() -> Command {
+    Command::new("month")
+        .about("Display the time tracked this month")
+        .long_about("This subcommand is used to display the time tracked by every single entry for the current month, broken down by week, along with daily averages")
+        .arg(
+            Arg::new("months-back")
+                .help("The number of months back to check")
+                .short('m')
+                .short_alias('p')
+                .long("months-back")
+                .value_parser(value_parser!(usize))
+                .action(ArgAction::Set),
+        )
+}
+
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    let months_back = arg_matches.get_one::<usize>("months-back").copied().unwrap_or(0);
+    let date = today.first_of_month().checked_sub((months_back as i64).months())?;
+    let syrspan = SyrSpan::from_start_and_end(date, date.last_of_month());
+    let title = format!("{} {}", MONTH_NAMES[date.month() as usize - 1], date.year());
+    print_table(arg_matches, entries, syrspan, Granularity::Week, &title, today)
+}
+
+/// Prints a table of the time tracked by each entry within the span, with one column per bucket followed by the total and daily average
+pub(super) fn print_table(
+    arg_matches: &ArgMatches,
+    entries: &Entries,
+    syrspan: SyrSpan,
+    granularity: Granularity,
+    title: &str,
+    today: &SyrDate,
+) -> Result<()> {
+    let buckets = granularity.buckets(syrspan);
+
+    let output = OutputFormat::from_arg_matches(arg_matches);
+    if !output.is_pretty() {
+        let mut records = buckets
+            .iter()
+            .flat_map(|bucket| {
+                entries
+                    .iter()
+                    .filter_map(move |entry| Some(Record::new(entry, Some(&bucket.start), bucket.duration_opt(&entry.blocs)?)))
+            })
+            .collect_vec();
+        crate::output::sort(&mut records);
+        return crate::output::emit(output, &records);
+    }
+
+    // days still to come are not counted in the average
+    let elapsed_days = SyrSpan::from_start_and_end(syrspan.start, syrspan.end.min(**today))
+        .into_iter()
+        .count()
+        .max(1);
+
+    type Row<'a> = (&'a str, Option<&'a str>, Vec<f64>, f64);
+    let mut rows: Vec<Row> = entries
+        .iter()
+        .filter_map(|entry| {
+            let durations = buckets.iter().map(|bucket| bucket.duration(&entry.blocs)).collect_vec();
+            let total = durations.iter().fold(0.0, |total, duration| total + duration);
+            (total > 0.0).then(|| (entry.name.as_str(), entry.aliases.first().map(String::as_str), durations, total))
+        })
+        .collect();
+    rows.sort_by(|a, b| config::Config::get().sort_option.compare((a.0, a.3), (b.0, b.3)));
+
+    let headers = buckets
+        .iter()
+        .map(|bucket| match granularity {
+            Granularity::Day => WeekdayFormatting::to_string(&bucket.start.weekday())[..3].to_string(),
+            _ => bucket.label.split(' ').next().unwrap_or_default().to_string(),
+        })
+        .collect_vec();
+    let pad = rows
+        .iter()
+        .map(|(name, alias, _, _)| name.len() + alias.map(|alias| alias.len() + 2).unwrap_or(0))
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    let totals = buckets
+        .iter()
+        .map(|bucket| entries.iter().fold(0.0, |total, entry| total + bucket.duration(&entry.blocs)))
+        .collect_vec();
+    let cell = |duration: &f64| match *duration {
+        0.0 => "-".to_string(),
+        duration => duration.s_str(),
+    };
+    let column = rows
+        .iter()
+        .flat_map(|(_, _, durations, _)| durations.iter())
+        .chain(totals.iter())
+        .map(|duration| cell(duration).len())
+        .chain(headers.iter().map(String::len))
+        .max()
+        .unwrap_or(0);
+    let cells = |durations: &[f64]| durations.iter().map(|duration| format!("{:>column$}", cell(duration))).join(" ");
+    let total = totals.iter().fold(0.0, |total, duration| total + duration);
+    // the grand total is the longest duration printed, but may be shorter than the headers
+    let width = total.s_str().len().max(f64::s_str_length()).max("Per day".len());
+
+    let header = format!(
+        "{:<pad$} {} {:>width$} {:>width$}",
+        "",
+        headers.iter().map(|header| format!("{:>column$}", header)).join(" "),
+        "Total",
+        "Per day",
+    );
+    let dashes = format!("{:-<1$}", "", header.len().max(title.len()));
+    println!("{}\n{}", title.bold(), dashes.as_str().dim());
+    println!("{}", header.dim());
+
+    for (name, alias, durations, total) in rows.iter() {
+        let title = match alias {
+            Some(alias) => format!("{:<width$}", format!("{}; {}", name, alias.dim()), width = pad + 8),
+            None => format!("{:<pad$}", name),
+        };
+        println!(
+            "{} {} {:>width$} {:>width$}",
+            title,
+            cells(durations),
+            total.s_str(),
+            (total / elapsed_days as f64).s_str()
+        );
+    }
+
+    println!("{}", dashes.as_str().dim());
+    println!(
+        "{:<pad$} {} {} {:>width$}",
+        "Total",
+        cells(&totals),
+        format!("{:>width$}", total.s_str()).bold(),
+        (total / elapsed_days as f64).s_str()
+    );
+    Ok(())
+}
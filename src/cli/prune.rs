@@ -10,6 +10,7 @@ pub(super) fn subcommand() -> Command {
                 .index(1)
                 .required(true)
                 .action(ArgAction::Set)
+                .allow_hyphen_values(true)
                 .value_parser(clap::value_parser!(SyrDate)),
         )
 }
@@ -8,17 +8,15 @@ pub(super) fn subcommand() -> Command {
         .arg(
             Arg::new("entry")
                 .index(1)
-                .required(true)
-                .help("The entry to remove")
+                .required(false)
+                .help("The entry to remove, an interactive picker is opened when omitted")
                 .action(ArgAction::Set),
         )
 }
 
-pub fn process(arg_matches: &ArgMatches, entries: &Entries) -> Result<()> {
-    let name = arg_matches
-        .get_one::<String>("entry")
-        .ok_or_eyre("Failed to parse entry to string")?;
-    if let Some(entry) = entries.choose(&name.to_uppercase(), IndexOptions::Indexed) {
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    let name = arg_matches.get_one::<String>("entry").map(String::as_str).unwrap_or_default();
+    if let Some(entry) = entries.choose(&name.to_uppercase(), IndexOptions::Indexed, selection_mode(arg_matches), today)? {
         entry.delete()?;
     };
     Ok(())
@@ -8,24 +8,26 @@ pub(super) fn subcommand() -> Command {
         .arg(
             Arg::new("entry")
                 .index(1)
-                .required(true)
-                .help("The entry to start")
+                .required(false)
+                .help("The entry to start, an interactive picker is opened when omitted")
                 .action(ArgAction::Set),
         )
 }
 
 pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
-    let name = arg_matches
-        .get_one::<String>("entry")
-        .ok_or_eyre("Failed to parse entry to string")?;
+    let name = arg_matches.get_one::<String>("entry").map(String::as_str).unwrap_or_default();
 
-    let Some(mut entry) = entries.choose(&name.to_uppercase(), IndexOptions::Indexed) else {
+    let Some(mut entry) = entries.choose(&name.to_uppercase(), IndexOptions::Indexed, selection_mode(arg_matches), today)? else {
         return Ok(());
     };
     // start of initialization
     let mut file_save_error_counter: u8 = 0;
     let frame_period = config::Config::get().frame_period;
-    let mut animation = animation::Animation::construct(config::Config::get().animation.clone(), f64::MS_STR_LENGTH, f64::MS_STR_LENGTH);
+    let mut animation = animation::Animation::construct(
+        config::Config::get().animation.clone(),
+        f64::live_str_length(),
+        f64::live_str_length(),
+    );
     let start = Instant::now();
     let mut instant = start;
     let mut autosave_instant = start;
@@ -34,15 +36,13 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
     enter_clean_input_mode();
     // end of initialization
     loop {
-        animation.step(&mut stdout, &instant.duration_since(start).as_secs_f64().ms_str());
-        if event::poll(std::time::Duration::from_millis(frame_period))? {
-            if let event::Event::Key(key) = event::read()? {
-                if key.kind == event::KeyEventKind::Press
-                    && (key.code == event::KeyCode::Char('q') || key.code == event::KeyCode::Char('Q') || key.code == event::KeyCode::Enter)
-                {
-                    break;
-                }
-            }
+        animation.step(&mut stdout, &instant.duration_since(start).as_secs_f64().live_str());
+        if event::poll(std::time::Duration::from_millis(frame_period))?
+            && let event::Event::Key(key) = event::read()?
+            && key.kind == event::KeyEventKind::Press
+            && (key.code == event::KeyCode::Char('q') || key.code == event::KeyCode::Char('Q') || key.code == event::KeyCode::Enter)
+        {
+            break;
         }
         if instant.duration_since(autosave_instant) > autosave_perdiod {
             if let Err(error) = entry.save() {
@@ -60,5 +60,6 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
         instant = new_instant;
     }
     exit_clean_input_mode();
-    entry.save()
+    entry.save()?;
+    Sessions::record(&entry.name, today, instant.duration_since(start).as_secs_f64())
 }
@@ -0,0 +1,129 @@
+use super::*;
+use crate::data::stats::Stats;
+
+pub(super) fn subcommand() -> Command {
+    Command::new("stats")
+        .aliases(["statistics", "streak"])
+        .about("Display streaks and consistency statistics")
+        .long_about("This subcommand is used to display the current and longest streaks, active days, mean and median daily time, busiest weekday and best week of every entry and overall\nthe past year is used when no span is specified\naliases: 'statistics', 'streak'")
+        .args(span_args())
+        .arg(
+            Arg::new("entry")
+                .help("Only display the statistics of this entry")
+                .long("entry")
+                .action(ArgAction::Set),
+        )
+}
+
+#[derive(Serialize)]
+struct NamedStats<'a> {
+    /// None for the overall statistics
+    name: Option<&'a str>,
+    #[serde(flatten)]
+    stats: Stats,
+}
+
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    let date_span = match date_span(arg_matches, today)? {
+        Some(date_span) => date_span,
+        None => SyrSpan::from_end_and_days_back(**arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today), 364),
+    };
+
+    let entries: Vec<Entry> = match arg_matches.get_one::<String>("entry") {
+        Some(query) => match entries.choose(&query.to_uppercase(), IndexOptions::All, selection_mode(arg_matches), today)? {
+            Some(entry) => vec![entry],
+            None => return Ok(()),
+        },
+        None => entries.to_vec(),
+    };
+
+    let mut per_entry = entries
+        .iter()
+        .map(|entry| NamedStats {
+            name: Some(entry.name.as_str()),
+            stats: Stats::new(date_span, |date| entry.get_bloc_duration(date)),
+        })
+        .filter(|named| named.stats.active_days > 0)
+        .collect_vec();
+    per_entry.sort_by(|a, b| {
+        config::Config::get().sort_option.compare(
+            (a.name.unwrap_or_default(), a.stats.total),
+            (b.name.unwrap_or_default(), b.stats.total),
+        )
+    });
+    // the overall statistics would only repeat those of a single entry
+    let overall = (per_entry.len() > 1).then(|| NamedStats {
+        name: None,
+        stats: Stats::new(date_span, |date| {
+            entries.iter().fold(0.0, |total, entry| total + entry.get_bloc_duration(date))
+        }),
+    });
+    let all = overall.into_iter().chain(per_entry).collect_vec();
+
+    match OutputFormat::from_arg_matches(arg_matches) {
+        OutputFormat::Pretty => (),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&all)?);
+            return Ok(());
+        }
+        // the overall statistics have an empty name
+        output => {
+            let header = std::iter::once("name").chain(Stats::FIELDS).collect_vec();
+            let rows = all
+                .iter()
+                .map(|named| {
+                    std::iter::once(named.name.unwrap_or_default().to_string())
+                        .chain(named.stats.fields())
+                        .collect()
+                })
+                .collect_vec();
+            return crate::output::emit_rows(output, &header, &rows);
+        }
+    }
+
+    if all.is_empty() {
+        println!(
+            "Nothing was tracked from {} to {}",
+            SyrDate::from(date_span.start),
+            SyrDate::from(date_span.end)
+        );
+        return Ok(());
+    }
+
+    for NamedStats { name, stats } in all.iter() {
+        let title = name.unwrap_or("Overall");
+        let days = |count: usize| format!("{} day{}", count, if count == 1 { "" } else { "s" });
+        let mut lines = vec![
+            ("Current streak", days(stats.current_streak)),
+            (
+                "Longest streak",
+                match stats.longest_streak_dates {
+                    Some((start, end)) => format!("{}, {} - {}", days(stats.longest_streak), start, end),
+                    None => days(0),
+                },
+            ),
+            ("Active days", format!("{} of {}", stats.active_days, stats.days)),
+            ("Mean per active day", stats.mean.s_str()),
+            ("Median per active day", stats.median.s_str()),
+        ];
+        if let Some((weekday, seconds)) = stats.busiest_weekday.as_ref() {
+            lines.push(("Busiest weekday", format!("{}, {} on average", weekday, seconds.s_str())));
+        }
+        if let Some((label, seconds)) = stats.best_week.as_ref() {
+            lines.push(("Best week", format!("{}, {}", label, seconds.s_str())));
+        }
+
+        let pad = lines.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let dashes = format!(
+            "{:-<1$}",
+            "",
+            lines.iter().map(|(_, value)| pad + 3 + value.len()).max().unwrap_or(0)
+        );
+        println!("{}\n{}", title.bold(), dashes.as_str().dim());
+        for (label, value) in lines {
+            println!("{:<pad$} : {}", label, value);
+        }
+        println!("{} {}\n", ARROWHEAD.dark_green(), stats.total.s_str());
+    }
+    Ok(())
+}
@@ -14,109 +14,187 @@ pub(super) fn subcommand() -> Command {
                 .num_args(1..20)
                 .action(ArgAction::Set),
         )
+        .args(span_args())
         .arg(
-            Arg::new("days-back")
-                .help("The number of days back included")
-                .short('d')
-                .long("days")
-                .alias("days-back")
-                .value_parser(value_parser!(usize))
-                .action(ArgAction::Set)
-                .group("days-back-group"),
-        )
-        .arg(
-            Arg::new("start-date")
-                .help("start date")
-                .short('s')
-                .long("start")
-                .alias("start-date")
-                .action(ArgAction::Set)
-                .value_parser(clap::value_parser!(SyrDate)),
-        )
-        .arg(
-            Arg::new("end-date")
-                .help("end date")
-                .short('l')
-                .long("end")
-                .alias("end-date")
-                .action(ArgAction::Set)
-                .value_parser(clap::value_parser!(SyrDate)),
+            Arg::new("granularity")
+                .help("Break the sum down by day, week or month")
+                .short('g')
+                .long("granularity")
+                .value_parser(value_parser!(Granularity))
+                .action(ArgAction::Set),
         )
-        .group(ArgGroup::new("days-back-group").conflicts_with("start-date"))
+        .args(rounding_args())
+}
+
+/// The round, rounding and round-per arguments overriding the configured billing rounding, shared with invoice
+pub(super) fn rounding_args() -> [Arg; 3] {
+    [
+        Arg::new("round")
+            .help("Round the billable time per entry per day or per session to this number of minutes, overriding the configuration")
+            .long("round")
+            .value_parser(value_parser!(u32))
+            .action(ArgAction::Set),
+        Arg::new("rounding")
+            .help("The way billable time is rounded, overriding the configuration")
+            .long("rounding")
+            .value_parser(value_parser!(RoundingMode))
+            .action(ArgAction::Set),
+        Arg::new("round-per")
+            .help("Whether the daily total of each entry or each session is rounded on its own, overriding the configuration")
+            .long("round-per")
+            .value_parser(value_parser!(RoundingUnit))
+            .action(ArgAction::Set),
+    ]
+}
+
+/// Returns the configured billing rounding, overridden by the round, rounding and round-per arguments
+pub(super) fn rounding(arg_matches: &ArgMatches) -> Rounding {
+    let config = config::Config::get().billing_rounding;
+    Rounding {
+        increment_minutes: arg_matches.get_one::<u32>("round").copied().unwrap_or(config.increment_minutes),
+        mode: arg_matches.get_one::<RoundingMode>("rounding").copied().unwrap_or(config.mode),
+        per: arg_matches.get_one::<RoundingUnit>("round-per").copied().unwrap_or(config.per),
+    }
 }
 
 pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
     let entries: Vec<&Entry> = match arg_matches.get_many::<String>("exclude") {
         Some(entry_match) => {
+            let selection_mode = selection_mode(arg_matches);
             let excluded: Vec<String> = entry_match
-                .flat_map(|s| entries.choose(&s.to_uppercase(), IndexOptions::All).map(|entry| entry.name))
-                .collect();
+                .map(|s| entries.choose(&s.to_uppercase(), IndexOptions::All, selection_mode, today))
+                .flatten_ok()
+                .map_ok(|entry| entry.name)
+                .collect::<Result<_>>()?;
 
             entries.iter().filter(|entry| !excluded.contains(&entry.name)).collect()
         }
         None => entries.as_inner(),
     };
 
-    let date_span: Vec<SyrDate> = {
-        // days-back + specified end-date or not
-        if let Some(num) = arg_matches.get_one::<usize>("days-back") {
-            let end_date = *arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today);
-            SyrSpan::from_end_and_days_back(*end_date, *num as i64)
-        }
-        // start-date + specified end-date or not
-        else if let Some(start_date) = arg_matches.get_one::<SyrDate>("start-date") {
-            let end_date = *arg_matches.get_one::<SyrDate>("end-date").unwrap_or(today);
+    let date_span = date_span(arg_matches, today)?.ok_or_eyre("A number of days back or a start date is required")?;
 
-            if *start_date > end_date {
-                bail!("Start date is more recent than end date");
-            }
-            SyrSpan::from_start_and_end(**start_date, *end_date)
-        } else {
-            bail!("Invalid subcommand usage");
-        }
+    // without a granularity, the whole span is summed up at once
+    let granularity = arg_matches.get_one::<Granularity>("granularity").copied();
+    let buckets = match granularity {
+        Some(granularity) => granularity.buckets(date_span),
+        None => vec![Bucket {
+            start: date_span.start.into(),
+            end: date_span.end.into(),
+            label: String::new(),
+        }],
+    };
+
+    let rounding = rounding(arg_matches);
+    let sessions: Vec<Sessions> = entries.iter().map(|entry| rounding.sessions(entry)).collect::<Result<_>>()?;
+    let output = OutputFormat::from_arg_matches(arg_matches);
+    if !output.is_pretty() {
+        let mut records = buckets
+            .iter()
+            .flat_map(|bucket| {
+                let date = granularity.map(|_| &bucket.start);
+                entries.iter().zip(sessions.iter()).filter_map(move |(entry, sessions)| {
+                    let record = Record::new(entry, date, bucket.duration_opt(&entry.blocs)?);
+                    Some(match rounding.is_active() {
+                        true => record.with_billable(rounding.billable(bucket, &entry.blocs, sessions)),
+                        false => record,
+                    })
+                })
+            })
+            .collect_vec();
+        crate::output::sort(&mut records);
+        return crate::output::emit(output, &records);
     }
-    .into_iter()
-    .collect();
 
-    type CompactOutput<'a> = (Vec<(&'a str, Option<&'a str>, f64)>, usize, f64);
-    let (mut bones, pad, total_hours): CompactOutput = entries
-        .into_iter()
-        .filter_map(|entry| {
-            let hours: f64 = date_span
-                .iter()
-                .filter_map(|date| entry.get_block_duration_opt(date))
-                .sum1()
-                .map(|val: f64| val / 3600.0)?;
-            let name: &str = entry.name.as_str();
-            let alias: Option<&str> = entry.aliases.first().map(String::as_str);
-            let padding = name.len() + alias.map(str::len).unwrap_or(0) + 2;
-            Some((name, alias, hours, padding))
-        })
-        .fold(
-            (Vec::new(), 0, 0.0),
-            |(mut output, pad, total_hours), (name, alias, hours, pad_)| {
-                output.push((name, alias, hours));
-                (output, pad.max(pad_), total_hours + hours)
-            },
-        );
+    // sums are displayed in decimal hours unless another format was chosen
+    let time_format = TimeFormat::current().unwrap_or(TimeFormat::Decimal);
+    let billable = |billable_duration: f64| match rounding.is_active() {
+        true => format!(" ({} billable)", time_format.format(billable_duration)),
+        false => String::new(),
+    };
+    let (mut total_duration, mut total_billable_duration): (f64, f64) = (0.0, 0.0);
+    for bucket in buckets.iter() {
+        type CompactOutput<'a> = (Vec<(&'a str, Option<&'a str>, f64, f64)>, usize, f64, f64);
+        let (mut bones, pad, bucket_duration, bucket_billable_duration): CompactOutput = entries
+            .iter()
+            .zip(sessions.iter())
+            .filter_map(|(entry, sessions)| {
+                let duration: f64 = bucket.duration_opt(&entry.blocs)?;
+                let billable_duration: f64 = rounding.billable(bucket, &entry.blocs, sessions);
+                let name: &str = entry.name.as_str();
+                let alias: Option<&str> = entry.aliases.first().map(String::as_str);
+                let padding = name.len() + alias.map(str::len).unwrap_or(0) + 2;
+                Some((name, alias, duration, billable_duration, padding))
+            })
+            .fold(
+                (Vec::new(), 0, 0.0, 0.0),
+                |(mut output, pad, total_duration, total_billable_duration), (name, alias, duration, billable_duration, pad_)| {
+                    output.push((name, alias, duration, billable_duration));
+                    (
+                        output,
+                        pad.max(pad_),
+                        total_duration + duration,
+                        total_billable_duration + billable_duration,
+                    )
+                },
+            );
 
-    bones.sort_by(|a, b| match config::Config::get().sort_option {
-        SortOptions::NameAscending => a.0.cmp(b.0),
-        SortOptions::NameDescending => b.0.cmp(a.0),
-        SortOptions::DurationAscending => a.2.total_cmp(&b.2),
-        SortOptions::DurationDescending => b.2.total_cmp(&a.2),
-    });
+        bones.sort_by(|a, b| config::Config::get().sort_option.compare((a.0, a.2), (b.0, b.2)));
 
-    bones.into_iter().for_each(|(name, alias, hours)| match alias {
-        Some(alias) => {
-            let title: String = format!("{}; {}", name, alias.dim());
-            println!("{:<width$} : {:.2}", title, hours, width = pad + 8);
+        if granularity.is_some() {
+            let dashes = format!("{:-<1$}", "", usize::max(pad + 3 + time_format.width(), bucket.label.len() + 13));
+            println!("{}\n{}", bucket.label.as_str().bold(), dashes.as_str().dim());
         }
-        None => {
-            println!("{:<width$} : {:.2}", name, hours, width = pad);
+
+        bones
+            .into_iter()
+            .for_each(|(name, alias, duration, billable_duration)| match alias {
+                Some(alias) => {
+                    let title: String = format!("{}; {}", name, alias.dim());
+                    println!(
+                        "{:<width$} : {}{}",
+                        title,
+                        time_format.format(duration),
+                        billable(billable_duration),
+                        width = pad + 8
+                    );
+                }
+                None => {
+                    println!(
+                        "{:<width$} : {}{}",
+                        name,
+                        time_format.format(duration),
+                        billable(billable_duration),
+                        width = pad
+                    );
+                }
+            });
+
+        if granularity.is_some() {
+            println!(
+                "{} {}{}\n",
+                ARROWHEAD.dark_green(),
+                time_format.format(bucket_duration),
+                billable(bucket_billable_duration)
+            );
         }
-    });
+        total_duration += bucket_duration;
+        total_billable_duration += bucket_billable_duration;
+    }
 
-    println!("{} {} Hours", ARROW.green(), format!("{:.2}", total_hours).bold());
+    match time_format {
+        TimeFormat::Decimal => println!(
+            "{} {} Hours{}",
+            ARROW.green(),
+            time_format.format(total_duration).bold(),
+            billable(total_billable_duration)
+        ),
+        _ => println!(
+            "{} {}{}",
+            ARROW.green(),
+            time_format.format(total_duration).bold(),
+            billable(total_billable_duration)
+        ),
+    }
     Ok(())
 }
@@ -33,6 +33,16 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
         date.into()
     };
 
+    let output = OutputFormat::from_arg_matches(arg_matches);
+    if !output.is_pretty() {
+        let mut records = entries
+            .iter()
+            .filter_map(|entry| Some(Record::new(entry, Some(&date), entry.get_block_duration_opt(&date)?)))
+            .collect_vec();
+        crate::output::sort(&mut records);
+        return crate::output::emit(output, &records);
+    }
+
     type CompactOutput<'a> = (Vec<(&'a str, Option<&'a str>, f64)>, usize, f64);
     let (mut bones, pad, total_duration): CompactOutput = entries
         .iter()
@@ -51,15 +61,10 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
             },
         );
 
-    bones.sort_by(|a, b| match config::Config::get().sort_option {
-        SortOptions::NameAscending => a.0.cmp(b.0),
-        SortOptions::NameDescending => b.0.cmp(a.0),
-        SortOptions::DurationAscending => a.2.total_cmp(&b.2),
-        SortOptions::DurationDescending => b.2.total_cmp(&a.2),
-    });
+    bones.sort_by(|a, b| config::Config::get().sort_option.compare((a.0, a.2), (b.0, b.2)));
 
     let weekday = date.weekday().to_string();
-    let dashes = format!("{:-<1$}", "", usize::max(pad + 3 + f64::S_STR_LENGTH, weekday.len() + 13));
+    let dashes = format!("{:-<1$}", "", usize::max(pad + 3 + f64::s_str_length(), weekday.len() + 13));
     println!(
         "{}\n{}",
         (weekday + " - " + date.to_string().as_str()).bold(),
@@ -77,6 +82,7 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
     });
 
     println!("{} {}", ARROW.green(), total_duration.s_str().bold());
+    goals::print_progress(entries, date, false);
 
     Ok(())
 }
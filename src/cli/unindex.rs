@@ -14,13 +14,15 @@ pub(super) fn subcommand() -> Command {
         )
 }
 
-pub fn process(arg_matches: &ArgMatches, entries: &Entries) -> Result<()> {
-    for mut entry in arg_matches
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    let selection_mode = selection_mode(arg_matches);
+    for name in arg_matches
         .get_many::<String>("entries")
         .ok_or_eyre("Failed to parse entry to string")?
-        .filter_map(|name| entries.choose(&name.to_uppercase(), IndexOptions::Indexed))
     {
-        entry.inverse_indexability()?;
+        if let Some(mut entry) = entries.choose(&name.to_uppercase(), IndexOptions::Indexed, selection_mode, today)? {
+            entry.inverse_indexability()?;
+        }
     }
     Ok(())
 }
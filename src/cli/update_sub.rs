@@ -8,10 +8,27 @@ pub(super) fn subcommand() -> Command {
         .arg(
             Arg::new("entry")
                 .index(1)
-                .required(true)
-                .help("The entry to update")
+                .required(false)
+                .help("The entry to update, an interactive picker is opened when omitted")
+                .allow_hyphen_values(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("duration")
+                .index(2)
+                .required(false)
+                .help("The duration to subtract, e.g. '1h30m', '90m', '1:30', '1.5h' or '-15m'")
+                .allow_hyphen_values(true)
+                .value_parser(parse_duration)
+                .conflicts_with_all(["hours", "minutes", "seconds"])
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new("set")
+                .help("Set the time tracked on the day to the duration instead")
+                .long("set")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("days-back")
                 .help("The number of days back to target")
@@ -25,6 +42,7 @@ pub(super) fn subcommand() -> Command {
                 .required(false)
                 .help("The target date")
                 .long("date")
+                .allow_hyphen_values(true)
                 .value_parser(value_parser!(SyrDate))
                 .action(ArgAction::Set)
                 .group("date-group"),
@@ -70,20 +88,14 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
         }
     };
 
-    let name = arg_matches
-        .get_one::<String>("entry")
-        .ok_or_eyre("Failed to parse entry to string")?;
-    let Some(mut entry) = entries.choose(&name.to_uppercase(), IndexOptions::Indexed) else {
+    let (name, duration) = update_add::entry_and_duration(arg_matches)?;
+    let Some(mut entry) = entries.choose(&name.to_uppercase(), IndexOptions::Indexed, selection_mode(arg_matches), today)? else {
         return Ok(());
     };
 
-    let hour_diff: f64 = *arg_matches.get_one::<f64>("hours").unwrap_or(&0.0);
-    let minute_diff: f64 = *arg_matches.get_one::<f64>("minutes").unwrap_or(&0.0);
-    let second_diff: f64 = *arg_matches.get_one::<f64>("seconds").unwrap_or(&0.0);
-    let total_diff: f64 = hour_diff * 3600.0 + minute_diff * 60.0 + second_diff;
-
     let past = entry.get_bloc_duration(&date);
-    entry.decrease_bloc_duration(&date, total_diff);
+    let set = arg_matches.get_flag("set");
+    entry.update_bloc_duration(&date, if set { duration } else { -duration }, set)?;
     entry.save()?;
     println!(
         "{} | {} {} {}",
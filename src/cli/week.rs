@@ -14,29 +14,48 @@ pub(super) fn subcommand() -> Command {
                 .value_parser(value_parser!(usize))
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new("matrix")
+                .help("Display a single table with entries as rows and days as columns")
+                .short('m')
+                .long("matrix")
+                .action(ArgAction::SetTrue),
+        )
 }
 
 pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
     let syrspan: SyrSpan = {
-        let date = if let Some(val) = arg_matches.get_one::<usize>("weeks-back") {
-            let val = if !matches!(today.weekday(), Weekday::Monday) {
-                -((val + 1) as i32)
-            } else {
-                -(*val as i32)
-            };
-            today.nth_weekday(val, Weekday::Monday)?
-        } else {
-            **today
-        };
+        let weeks_back = arg_matches.get_one::<usize>("weeks-back").copied().unwrap_or(0);
+        SyrSpan::week_of(today.checked_sub((weeks_back as i64 * 7).days())?)?
+    };
 
-        let (start, end) = match date.weekday() {
-            Weekday::Monday => (date, date.nth_weekday(1, Weekday::Sunday)?),
-            Weekday::Sunday => (date.nth_weekday(-1, Weekday::Monday)?, date),
-            _ => (date.nth_weekday(-1, Weekday::Monday)?, date.nth_weekday(1, Weekday::Sunday)?),
-        };
+    let (year, week) = syrspan.iso_week();
+    let title = format!(
+        "Week {}, {} ({} - {})",
+        week,
+        year,
+        SyrDate::from(syrspan.start),
+        SyrDate::from(syrspan.end)
+    );
+    if arg_matches.get_flag("matrix") {
+        return month::print_table(arg_matches, entries, syrspan, Granularity::Day, &title, today);
+    }
+
+    let output = OutputFormat::from_arg_matches(arg_matches);
+    if !output.is_pretty() {
+        let mut records = syrspan
+            .into_iter()
+            .flat_map(|date| {
+                entries
+                    .iter()
+                    .filter_map(move |entry| Some(Record::new(entry, Some(&date), entry.get_block_duration_opt(&date)?)))
+            })
+            .collect_vec();
+        crate::output::sort(&mut records);
+        return crate::output::emit(output, &records);
+    }
+    println!("{}\n", title.bold().underlined());
 
-        SyrSpan::from_start_and_end(start, end)
-    };
     let mut total_weekly_duration: f64 = 0.0;
     for date in syrspan.into_iter() {
         type CompactOutput<'a> = (Vec<(&'a str, Option<&'a str>, f64)>, usize, f64);
@@ -57,15 +76,10 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
                 },
             );
 
-        bones.sort_by(|a, b| match config::Config::get().sort_option {
-            SortOptions::NameAscending => a.0.cmp(b.0),
-            SortOptions::NameDescending => b.0.cmp(a.0),
-            SortOptions::DurationAscending => a.2.total_cmp(&b.2),
-            SortOptions::DurationDescending => b.2.total_cmp(&a.2),
-        });
+        bones.sort_by(|a, b| config::Config::get().sort_option.compare((a.0, a.2), (b.0, b.2)));
 
         let weekday = date.weekday().to_string();
-        let dashes = format!("{:-<1$}", "", usize::max(pad + 3 + f64::S_STR_LENGTH, weekday.len() + 13));
+        let dashes = format!("{:-<1$}", "", usize::max(pad + 3 + f64::s_str_length(), weekday.len() + 13));
         println!(
             "{}\n{}",
             (weekday + " - " + date.to_string().as_str()).bold(),
@@ -85,5 +99,7 @@ pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) ->
         total_weekly_duration += total_daily_duration;
     }
     println!("{} {}", ARROW.green(), total_weekly_duration.s_str().bold());
+    // goals are measured over the periods containing the last day of past weeks, which daily goals are labelled with
+    goals::print_progress(entries, SyrDate::from(syrspan.end.min(**today)), true);
     Ok(())
 }
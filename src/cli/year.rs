@@ -0,0 +1,23 @@
+use super::*;
+
+pub(super) fn subcommand() -> Command {
+    Command::new("year")
+        .about("Display the time tracked this year")
+        .long_about("This subcommand is used to display the time tracked by every single entry for the current year, broken down by month, along with daily averages")
+        .arg(
+            Arg::new("years-back")
+                .help("The number of years back to check")
+                .short('y')
+                .short_alias('p')
+                .long("years-back")
+                .value_parser(value_parser!(usize))
+                .action(ArgAction::Set),
+        )
+}
+
+pub fn process(arg_matches: &ArgMatches, entries: &Entries, today: &SyrDate) -> Result<()> {
+    let years_back = arg_matches.get_one::<usize>("years-back").copied().unwrap_or(0);
+    let year = today.year() - i16::try_from(years_back)?;
+    let syrspan = SyrSpan::from_start_and_end(jiff::civil::Date::new(year, 1, 1)?, jiff::civil::Date::new(year, 12, 31)?);
+    month::print_table(arg_matches, entries, syrspan, Granularity::Month, &year.to_string(), today)
+}
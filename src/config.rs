@@ -1,13 +1,29 @@
-use crate::{animation::AnimationBuilder, cli::SortOptions, data::graphing::interpolation::InterpolationMethod};
+use crate::{
+    animation::AnimationBuilder,
+    cli::SortOptions,
+    data::{
+        Goal,
+        billing::Rounding,
+        graphing::{GraphFormat, GraphStyle, Theme, interpolation::InterpolationMethod},
+        invoice::Client,
+        syrtime::{DateFormat, TimeFormat},
+    },
+};
 use color_eyre::Result;
+use itertools::Itertools;
+use jiff::civil::Weekday;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     io::{Read, Write},
     sync::OnceLock,
 };
 
 pub static CONFIG: OnceLock<Config> = OnceLock::new();
 
+/// The name of the theme made of the colors set by the deprecated graph_*_rgb keys
+const LEGACY_THEME: &str = "legacy";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
@@ -21,17 +37,40 @@ pub struct Config {
     pub sort_option: SortOptions,
     /// Determines the numbers of hours past midnight for which running a command will count for the previous day.
     pub night_owl_hour_extension: i8,
+    /// Determines the first day of the week, used by every week-based report, e.g. "Monday", "Sunday" or "Saturday".
+    #[serde(with = "WeekdayDef")]
+    pub week_start: Weekday,
+    /// Determines how dates are displayed, "dd/mm/yyyy", "yyyy-mm-dd" or "mm/dd/yyyy", dates separated by '/' being parsed in the same order.
+    pub date_format: DateFormat,
+    /// Determines how durations are displayed, "hh:mm:ss", "hh:mm", "decimal" or "humanized", null keeping hh:mm:ss everywhere but in sum, which uses decimal hours.
+    pub time_format: Option<TimeFormat>,
+    /// The hours to track on entries every "Day", "Week" or "Month", e.g. {"entry": "THESIS", "hours": 10.0, "per": "Week"}.
+    pub goals: Vec<Goal>,
+    /// Determines how billable time is rounded, e.g. {"increment_minutes": 15, "mode": "Up", "per": "Day"} with the modes "Up", "Down" and "Nearest", "per" being "Day" or "Session", an increment of 0 disabling billable time.
+    pub billing_rounding: Rounding,
+    /// The clients invoices are made out to, e.g. {"name": "ACME", "entries": ["THESIS", "READING"], "rate": 80.0, "currency": "EUR", "entry_rates": {"READING": 60.0}}.
+    pub clients: Vec<Client>,
 
     /// The threshold for results to be considered.
     pub search_threshold: f64,
     /// The relative weights of the Smith-Waterman and Needlman-Wunsch algorithms respectively.
     pub sw_nw_ratio: f64,
     /// The match score used by both algorithms.
-    pub match_score: i16,
+    pub match_score: i32,
     /// The mismatch penalty used by both algorithms.
-    pub mismatch_penalty: i16,
+    pub mismatch_penalty: i32,
     /// The gap penalty used by both algorithms.
-    pub gap_penalty: i16,
+    pub gap_penalty: i32,
+    /// The bonus added when the query is the start of a name or alias.
+    pub prefix_bonus: f64,
+    /// The bonus added when the query is the start of one of the words of a name or alias.
+    pub word_boundary_bonus: f64,
+    /// The bonus added when the query matches the initials of the words of a name or alias, e.g. 'ML' for 'MACHINE-LEARNING'.
+    pub acronym_bonus: f64,
+    /// The maximum bonus given to entries tracked recently.
+    pub recency_weight: f64,
+    /// The maximum bonus given to entries tracked frequently over the past four weeks.
+    pub frequency_weight: f64,
 
     /// Determines how long in milliseconds a frame will be displayed before being refreshed.
     pub frame_period: u64,
@@ -40,24 +79,63 @@ pub struct Config {
 
     /// Determines the directory where graphs are saved, an empty string defaults to current directory.
     pub graph_output_dir: String,
-    /// Determines the interpolation method used, "Linear" and "Makima" are currently available.
+    /// Determines the file format graphs are saved as, "Png" and "Svg" are currently available.
+    pub graph_format: GraphFormat,
+    /// Determines how entries are drawn, "Line", "StackedBar", "StackedArea" and "Pie" are currently available.
+    pub graph_style: GraphStyle,
+    /// Determines the number of points averaged by the moving average overlay of graphs.
+    pub graph_moving_average_window: usize,
+    /// Determines the share, in percent, under which entries are grouped into "Other" in pie charts.
+    pub graph_pie_threshold: f64,
+    /// Determines the radius of the hole of pie charts relative to their radius, 0 draws a full pie.
+    pub graph_pie_hole: f64,
+    /// Determines the width of graphs in pixels at 96 DPI, 0 scales the width with the number of dates.
+    pub graph_width: u32,
+    /// Determines the height of graphs in pixels at 96 DPI.
+    pub graph_height: u32,
+    /// Determines the resolution of graphs, dimensions, fonts and lines are all scaled by graph_dpi / 96.
+    pub graph_dpi: u32,
+    /// Determines the interpolation method used, "Linear", "Makima", "Pchip", "NaturalCubic" and "Step" are currently available.
     pub graph_interpolation_method: InterpolationMethod,
     /// Determines the number of points between a date and the next one that will be interpolated.
     pub graph_nb_interpolated_points: usize,
     /// Determines the marker size for entries.
     pub graph_marker_size: u32,
-    /// Determines the background color of the graph.
-    pub graph_background_rgb: (u8, u8, u8),
-    /// Determines the foreground color of the graph.
-    pub graph_foreground_rgb: (u8, u8, u8),
-    /// Determines the bold grid color of the graph.
-    pub graph_coarse_grid_rgb: (u8, u8, u8),
-    /// Determines the fine grid color of the graph.
-    pub graph_fine_grid_rgb: (u8, u8, u8),
-    /// Determines the sum line color of the graph.
-    pub graph_sum_line_rgb: (u8, u8, u8),
-    /// Determines the colors used for entry markers.
-    pub graph_marker_rgb: Vec<(u8, u8, u8)>,
+    /// Determines the colors of graphs, "dark", "light", "high-contrast", "colorblind-safe" or the name of one of graph_themes.
+    pub graph_theme: String,
+    /// User-defined themes by name, with the colors background_rgb, foreground_rgb, coarse_grid_rgb, fine_grid_rgb, sum_line_rgb, heatmap_rgb and marker_rgb.
+    pub graph_themes: BTreeMap<String, Theme>,
+    /// Deprecated, the colors of graph_themes are used instead, colors other than the former defaults being moved to a theme named "legacy" on load.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_background_rgb: Option<(u8, u8, u8)>,
+    /// Deprecated, see graph_background_rgb.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_foreground_rgb: Option<(u8, u8, u8)>,
+    /// Deprecated, see graph_background_rgb.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_coarse_grid_rgb: Option<(u8, u8, u8)>,
+    /// Deprecated, see graph_background_rgb.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_fine_grid_rgb: Option<(u8, u8, u8)>,
+    /// Deprecated, see graph_background_rgb.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_sum_line_rgb: Option<(u8, u8, u8)>,
+    /// Deprecated, see graph_background_rgb.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_marker_rgb: Option<Vec<(u8, u8, u8)>>,
+}
+
+/// Mirrors jiff's weekday, which does not implement serde's traits
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Weekday")]
+enum WeekdayDef {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
 }
 
 impl Default for Config {
@@ -68,11 +146,22 @@ impl Default for Config {
             backup_path: "".to_string(),
             sort_option: SortOptions::default(),
             night_owl_hour_extension: 0,
+            week_start: Weekday::Monday,
+            date_format: DateFormat::default(),
+            time_format: None,
+            goals: Vec::new(),
+            billing_rounding: Rounding::default(),
+            clients: Vec::new(),
             search_threshold: 0.0,
             sw_nw_ratio: 0.5,
             match_score: 2,
             mismatch_penalty: -1,
             gap_penalty: -1,
+            prefix_bonus: 0.3,
+            word_boundary_bonus: 0.2,
+            acronym_bonus: 0.25,
+            recency_weight: 0.1,
+            frequency_weight: 0.1,
             frame_period: 150,
             animation: vec![
                 ("|  ".to_string(), "  |".to_string()),
@@ -81,28 +170,25 @@ impl Default for Config {
                 ("\\  ".to_string(), "  \\".to_string()),
             ],
             graph_output_dir: "".to_string(),
+            graph_format: GraphFormat::default(),
+            graph_style: GraphStyle::default(),
+            graph_moving_average_window: 7,
+            graph_pie_threshold: 3.0,
+            graph_pie_hole: 0.5,
+            graph_width: 0,
+            graph_height: 1080,
+            graph_dpi: 96,
             graph_interpolation_method: InterpolationMethod::Linear,
             graph_nb_interpolated_points: 1500,
             graph_marker_size: 6,
-            graph_background_rgb: (30, 30, 46),
-            graph_foreground_rgb: (205, 214, 244),
-            graph_coarse_grid_rgb: (84, 87, 108),
-            graph_fine_grid_rgb: (49, 50, 68),
-            graph_sum_line_rgb: (205, 214, 244),
-            graph_marker_rgb: vec![
-                // amaranth pink
-                (243, 167, 186),
-                // cocktail red
-                (253, 109, 114),
-                // deep saffron
-                (255, 150, 58),
-                // corn
-                (250, 234, 93),
-                // mountain lake green
-                (117, 185, 150),
-                // cerulean
-                (0, 143, 190),
-            ],
+            graph_theme: "dark".to_string(),
+            graph_themes: BTreeMap::new(),
+            graph_background_rgb: None,
+            graph_foreground_rgb: None,
+            graph_coarse_grid_rgb: None,
+            graph_fine_grid_rgb: None,
+            graph_sum_line_rgb: None,
+            graph_marker_rgb: None,
         }
     }
 }
@@ -113,7 +199,25 @@ impl Config {
     }
     pub fn load(filepath: &std::path::Path) -> Self {
         match Self::from_file(filepath) {
-            Ok(config) => config,
+            Ok(mut config) => {
+                if let Some(keys) = config.migrate_legacy_colors() {
+                    match config.to_file(filepath, true) {
+                        Ok(()) => eprintln!(
+                            "Warning: Moved the colors of the deprecated keys {} to the '{}' theme of graph_themes, at '{}'",
+                            keys,
+                            LEGACY_THEME,
+                            filepath.display()
+                        ),
+                        Err(error) => eprintln!(
+                            "Warning: Failed to move the colors of the deprecated keys {} to graph_themes, at '{}', caused by '{}'",
+                            keys,
+                            filepath.display(),
+                            error
+                        ),
+                    }
+                }
+                config
+            }
             Err(err) => {
                 eprintln!("Warning: Failed to load configuration from file, '{}'", err);
                 let config = Self::default();
@@ -121,7 +225,7 @@ impl Config {
                     return config;
                 };
                 if downcast_error.kind() == std::io::ErrorKind::NotFound {
-                    match config.to_file(filepath) {
+                    match config.to_file(filepath, false) {
                         Ok(()) => eprintln!("Warning: Created default configuration file, at '{}'", filepath.display()),
                         Err(error) => eprintln!(
                             "Warning: Failed to create default configuration file, at '{}', caused by '{}'",
@@ -145,11 +249,110 @@ impl Config {
         Ok(ijson::from_value(&serde_json::from_slice(&buffer)?)?)
     }
 
-    fn to_file(&self, filepath: &std::path::Path) -> Result<()> {
-        let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(filepath)?;
+    /// Moves the colors set by the deprecated graph_*_rgb keys to the "legacy" theme, selected unless another theme was chosen, returns the keys moved if the configuration changed
+    fn migrate_legacy_colors(&mut self) -> Option<String> {
+        let keys = [
+            ("graph_background_rgb", self.graph_background_rgb.is_some()),
+            ("graph_foreground_rgb", self.graph_foreground_rgb.is_some()),
+            ("graph_coarse_grid_rgb", self.graph_coarse_grid_rgb.is_some()),
+            ("graph_fine_grid_rgb", self.graph_fine_grid_rgb.is_some()),
+            ("graph_sum_line_rgb", self.graph_sum_line_rgb.is_some()),
+            ("graph_marker_rgb", self.graph_marker_rgb.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(key, is_set)| is_set.then_some(key))
+        .join(", ");
+        if keys.is_empty() {
+            return None;
+        }
+        let default = Theme::default();
+        let theme = Theme {
+            background_rgb: self.graph_background_rgb.take().unwrap_or(default.background_rgb),
+            foreground_rgb: self.graph_foreground_rgb.take().unwrap_or(default.foreground_rgb),
+            coarse_grid_rgb: self.graph_coarse_grid_rgb.take().unwrap_or(default.coarse_grid_rgb),
+            fine_grid_rgb: self.graph_fine_grid_rgb.take().unwrap_or(default.fine_grid_rgb),
+            sum_line_rgb: self.graph_sum_line_rgb.take().unwrap_or(default.sum_line_rgb),
+            marker_rgb: self.graph_marker_rgb.take().unwrap_or(default.marker_rgb.clone()),
+            ..default.clone()
+        };
+        // the default colors of older versions are those of the dark theme
+        if theme == default {
+            return None;
+        }
+        if self.graph_themes.contains_key(LEGACY_THEME) {
+            eprintln!(
+                "Warning: A '{}' theme is already defined, the colors of the deprecated keys {} are dropped",
+                LEGACY_THEME, keys
+            );
+            return Some(keys);
+        }
+        self.graph_themes.insert(LEGACY_THEME.to_string(), theme);
+        if self.graph_theme == Self::default().graph_theme {
+            self.graph_theme = LEGACY_THEME.to_string();
+        }
+        Some(keys)
+    }
+
+    /// Writes the configuration to the file, which must not exist unless overwritten
+    fn to_file(&self, filepath: &std::path::Path, overwrite: bool) -> Result<()> {
+        let mut file = match overwrite {
+            true => std::fs::File::create(filepath)?,
+            false => std::fs::OpenOptions::new().write(true).create_new(true).open(filepath)?,
+        };
 
         file.write_all(&serde_json::to_vec_pretty(&ijson::to_value(self)?)?)?;
         file.flush()?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_colors_form_a_theme() {
+        let mut config: Config = serde_json::from_str(
+            r#"{
+                "graph_background_rgb": [255, 255, 255],
+                "graph_marker_rgb": [[255, 0, 0], [0, 0, 255]],
+                "graph_marker_size": 6
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.migrate_legacy_colors().as_deref(),
+            Some("graph_background_rgb, graph_marker_rgb")
+        );
+        assert_eq!(config.graph_theme, LEGACY_THEME);
+        let theme = &config.graph_themes[LEGACY_THEME];
+        assert_eq!(theme.background_rgb, (255, 255, 255));
+        assert_eq!(theme.marker_rgb, [(255, 0, 0), (0, 0, 255)]);
+        assert_eq!(theme.foreground_rgb, Theme::default().foreground_rgb);
+        // the migrated configuration no longer holds the deprecated keys
+        assert!(config.graph_background_rgb.is_none() && config.migrate_legacy_colors().is_none());
+
+        let mut config: Config = serde_json::from_str(r#"{"graph_theme": "light", "graph_sum_line_rgb": [0, 0, 0]}"#).unwrap();
+        assert!(config.migrate_legacy_colors().is_some());
+        assert_eq!(config.graph_theme, "light");
+        assert!(config.graph_themes.contains_key(LEGACY_THEME));
+    }
+
+    #[test]
+    fn default_legacy_colors_are_dropped_silently() {
+        let mut config: Config = serde_json::from_str(
+            r#"{
+                "graph_background_rgb": [30, 30, 46],
+                "graph_foreground_rgb": [205, 214, 244],
+                "graph_coarse_grid_rgb": [84, 87, 108],
+                "graph_fine_grid_rgb": [49, 50, 68],
+                "graph_sum_line_rgb": [205, 214, 244],
+                "graph_marker_rgb": [[243, 167, 186], [253, 109, 114], [255, 150, 58], [250, 234, 93], [117, 185, 150], [0, 143, 190]]
+            }"#,
+        )
+        .unwrap();
+        assert!(config.migrate_legacy_colors().is_none());
+        assert_eq!(config.graph_theme, "dark");
+        assert!(config.graph_themes.is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use super::{
+    Entry,
+    sessions::Sessions,
+    syrtime::{Blocs, Bucket},
+};
+use clap::{ValueEnum, builder::PossibleValue};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+/// Determines which way durations are rounded to the increment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundingMode {
+    #[default]
+    Up,
+    Down,
+    Nearest,
+}
+
+impl ValueEnum for RoundingMode {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Up, Self::Down, Self::Nearest]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Up => PossibleValue::new("up"),
+            Self::Down => PossibleValue::new("down"),
+            Self::Nearest => PossibleValue::new("nearest"),
+        })
+    }
+}
+
+/// Determines what is rounded on its own, the daily total of an entry or each of its sessions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundingUnit {
+    #[default]
+    Day,
+    Session,
+}
+
+impl ValueEnum for RoundingUnit {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Day, Self::Session]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Day => PossibleValue::new("day"),
+            Self::Session => PossibleValue::new("session"),
+        })
+    }
+}
+
+/// Rounds the time tracked into billable time, per entry per day or per session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rounding {
+    /// The increment in minutes durations are rounded to, e.g. 6, 15 or 30, 0 disabling rounding
+    pub increment_minutes: u32,
+    pub mode: RoundingMode,
+    pub per: RoundingUnit,
+}
+
+impl Rounding {
+    pub fn is_active(&self) -> bool {
+        self.increment_minutes > 0
+    }
+
+    pub fn round(&self, seconds: f64) -> f64 {
+        if !self.is_active() || seconds <= 0.0 {
+            return seconds;
+        }
+        let increment = self.increment_minutes as f64 * 60.0;
+        let increments = seconds / increment;
+        increment
+            * match self.mode {
+                RoundingMode::Up => increments.ceil(),
+                RoundingMode::Down => increments.floor(),
+                RoundingMode::Nearest => increments.round(),
+            }
+    }
+
+    /// Returns the sessions of the entry when rounding per session, none otherwise
+    pub fn sessions(&self, entry: &Entry) -> Result<Sessions> {
+        match self.is_active() && self.per == RoundingUnit::Session {
+            true => Sessions::load(&entry.name),
+            false => Ok(Sessions::default()),
+        }
+    }
+
+    /// Returns the billable seconds within the bucket, each day or each session being rounded on its own
+    pub fn billable(&self, bucket: &Bucket, blocs: &Blocs, sessions: &Sessions) -> f64 {
+        blocs
+            .range(bucket.start..=bucket.end)
+            .fold(0.0, |total, (date, duration)| total + self.round_day(*duration, sessions.get(date)))
+    }
+
+    /// Rounds the sessions of the day on their own, the time added by hand being rounded as one more session
+    fn round_day(&self, duration: f64, sessions: Option<&Vec<f64>>) -> f64 {
+        let Some(sessions) = sessions.filter(|_| self.per == RoundingUnit::Session) else {
+            return self.round(duration);
+        };
+        let remainder = duration - sessions.iter().sum::<f64>();
+        // time was taken off the day, the sessions no longer add up to it
+        if remainder < -1.0 {
+            return self.round(duration);
+        }
+        sessions.iter().map(|session| self.round(*session)).sum::<f64>() + if remainder >= 1.0 { self.round(remainder) } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::syrtime::SyrDate;
+
+    #[test]
+    fn rounds_to_the_increment() {
+        let rounding = |increment_minutes, mode| Rounding {
+            increment_minutes,
+            mode,
+            per: RoundingUnit::Day,
+        };
+        assert_eq!(rounding(15, RoundingMode::Up).round(16.0 * 60.0), 30.0 * 60.0);
+        assert_eq!(rounding(15, RoundingMode::Down).round(29.0 * 60.0), 15.0 * 60.0);
+        assert_eq!(rounding(6, RoundingMode::Nearest).round(8.0 * 60.0), 6.0 * 60.0);
+        assert_eq!(rounding(6, RoundingMode::Nearest).round(10.0 * 60.0), 12.0 * 60.0);
+        assert_eq!(rounding(0, RoundingMode::Up).round(100.0), 100.0);
+        assert_eq!(rounding(30, RoundingMode::Up).round(0.0), 0.0);
+    }
+
+    #[test]
+    fn rounds_each_session() {
+        let date = SyrDate::from(jiff::civil::date(2026, 10, 5));
+        let bucket = Bucket {
+            start: date,
+            end: date,
+            label: String::new(),
+        };
+        let blocs = Blocs([(date, 25.0 * 60.0)].into_iter().collect());
+        let sessions = Sessions([(date, vec![10.0 * 60.0, 10.0 * 60.0])].into_iter().collect());
+        let rounding = |per| Rounding {
+            increment_minutes: 15,
+            mode: RoundingMode::Up,
+            per,
+        };
+        assert_eq!(rounding(RoundingUnit::Day).billable(&bucket, &blocs, &sessions), 30.0 * 60.0);
+        // two sessions and five minutes added by hand
+        assert_eq!(rounding(RoundingUnit::Session).billable(&bucket, &blocs, &sessions), 45.0 * 60.0);
+        // without sessions, the day is rounded as a whole
+        assert_eq!(
+            rounding(RoundingUnit::Session).billable(&bucket, &blocs, &Sessions::default()),
+            30.0 * 60.0
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use super::{Entry, sessions::Sessions, syrtime::SyrDate};
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, bail},
+};
+use std::{
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+/// A checked-in entry, stored as a '.cin' file containing the check-in timestamp
+pub struct CheckIn {
+    pub filepath: PathBuf,
+    pub name: String,
+    pub timestamp: jiff::Timestamp,
+}
+
+impl CheckIn {
+    /// Returns the checked-in entry if there is one, fails if multiple entries are checked-in
+    pub fn find() -> Result<Option<Self>> {
+        let filepaths: Vec<PathBuf> = std::fs::read_dir(crate::dirs::Dirs::get().data_dir())?
+            .filter_map(|filepath| {
+                let filepath = match filepath {
+                    Ok(e) => e,
+                    Err(err) => {
+                        eprintln!("Warning: {}", err);
+                        return None;
+                    }
+                }
+                .path();
+
+                if filepath.extension()?.to_str()? == "cin" {
+                    Some(filepath)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let filepath = match filepaths.len() {
+            0 => return Ok(None),
+            1 => filepaths[0].clone(),
+            _ => bail!("Multiple checked-in entries found"),
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        std::fs::OpenOptions::new().read(true).open(&filepath)?.read_to_end(&mut buffer)?;
+        let timestamp: jiff::Timestamp = ijson::from_value(&serde_json::from_slice(&buffer)?)?;
+
+        let name = filepath
+            .file_stem()
+            .ok_or_eyre("Invalid file name")?
+            .to_str()
+            .ok_or_eyre("Invalid file name")?
+            .to_string();
+
+        Ok(Some(Self { filepath, name, timestamp }))
+    }
+
+    /// Checks-in the entry, fails if another entry is already checked-in
+    pub fn create(entry: &Entry) -> Result<Self> {
+        if let Some(check_in) = Self::find()? {
+            bail!("Another entry is already checked-in: '{}'", check_in.filepath.display());
+        }
+
+        let filepath = crate::dirs::Dirs::get().data_dir().join([&entry.name, ".cin"].concat());
+        let timestamp = jiff::Timestamp::now();
+
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&filepath)?
+            .write_all(&serde_json::to_vec(&ijson::to_value(timestamp)?)?)?;
+
+        Ok(Self {
+            filepath,
+            name: entry.name.clone(),
+            timestamp,
+        })
+    }
+
+    /// Returns the number of seconds elapsed since the check-in
+    pub fn elapsed(&self) -> Result<f64> {
+        Ok(jiff::Timestamp::now().since(self.timestamp)?.abs().total(jiff::Unit::Second)?)
+    }
+
+    /// Adds the elapsed time to the entry, saves it, records the session and removes the check-in, returns the elapsed time
+    pub fn complete(self, entry: &mut Entry, date: &SyrDate) -> Result<f64> {
+        let elapsed = self.elapsed()?;
+        entry.increase_bloc_duration(date, elapsed);
+        entry.save()?;
+        Sessions::record(&entry.name, date, elapsed)?;
+        self.cancel()?;
+        Ok(elapsed)
+    }
+
+    /// Removes the check-in without adding the elapsed time to the entry
+    pub fn cancel(self) -> Result<()> {
+        std::fs::remove_file(&self.filepath).map_err(Into::into)
+    }
+}
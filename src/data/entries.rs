@@ -1,12 +1,14 @@
 use crate::{
-    algorithms,
+    algorithms::Matcher,
+    config::Config,
+    picker::Picker,
     utils::{ARROW, enter_clean_input_mode, exit_clean_input_mode},
 };
-use color_eyre::Result;
+use color_eyre::{Result, eyre::bail};
 use crossterm::{event, style::Stylize};
 use itertools::Itertools;
 
-use super::{Entry, IndexOptions};
+use super::{Entry, IndexOptions, SelectionMode, syrtime::SyrDate};
 
 pub struct Entries(Vec<Entry>);
 
@@ -58,9 +60,9 @@ impl Entries {
             .collect::<Vec<Entry>>()
             .into())
     }
-    pub fn choose(&self, query: &str, index_options: IndexOptions) -> Option<Entry> {
-        let sw_nw_ratio = crate::config::Config::get().sw_nw_ratio;
-        let choices: Vec<&Entry> = self
+    /// Returns the entry matching the query, an error is only returned when no entry can be selected non-interactively
+    pub fn choose(&self, query: &str, index_options: IndexOptions, selection_mode: SelectionMode, today: &SyrDate) -> Result<Option<Entry>> {
+        let candidates: Vec<&Entry> = self
             .iter()
             // Keeps only entries marked as indexed if indexed_exclusive is true
             .filter(|entry| match index_options {
@@ -68,43 +70,66 @@ impl Entries {
                 IndexOptions::Indexed => entry.indexed,
                 IndexOptions::Unindexed => !entry.indexed,
             })
-            .map(|entry| {
-                (
-                    entry
-                        .aliases
-                        .iter()
-                        .chain(std::iter::once(&entry.name))
-                        .map(|string| {
-                            sw_nw_ratio * algorithms::smith_waterman(string, query)
-                                + (1.0 - sw_nw_ratio) * algorithms::needleman_wunsch(string, query)
-                        })
-                        .fold(-1.0, |acc, x| if x > acc { x } else { acc }),
-                    entry,
-                )
-            })
+            .collect();
+
+        if query.is_empty() {
+            if selection_mode != SelectionMode::Interactive {
+                bail!("An entry must be specified when not running interactively");
+            }
+            return Ok(Self::confirm(Picker::new(candidates, query, today).pick()));
+        }
+
+        if selection_mode == SelectionMode::Exact {
+            let mut candidates = candidates
+                .iter()
+                .filter(|entry| entry.name == query || entry.aliases.iter().any(|alias| alias == query));
+            return match (candidates.next(), candidates.next()) {
+                (Some(entry), None) => {
+                    eprintln!("{} {}", ARROW.cyan().dim(), entry.name.as_str().dim());
+                    Ok(Some((*entry).clone()))
+                }
+                (Some(_), Some(_)) => bail!("Multiple entries are named or aliased '{query}'"),
+                (None, _) => bail!("No entry is named or aliased '{query}'"),
+            };
+        }
+
+        let mut matcher = Matcher::new(Config::get());
+        let choices: Vec<&Entry> = candidates
+            .iter()
+            .map(|entry| (matcher.score_entry(entry, query, today), *entry))
             // Keeps entries with a high enough score
-            .filter(|(score, _)| *score >= crate::config::Config::get().search_threshold)
+            .filter(|(score, _)| *score >= Config::get().search_threshold)
             // Sorts by score, highest at the top
             .sorted_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal))
-            // Keep only the top three scores
-            .take(3)
             .map(|(_, entry)| entry)
             .collect();
 
+        if selection_mode == SelectionMode::Best {
+            let Some(entry) = choices.first() else {
+                bail!("No entry matches '{query}'");
+            };
+            eprintln!("{} {}", ARROW.cyan().dim(), entry.name.as_str().dim());
+            return Ok(Some((*entry).clone()));
+        }
+
         let response = match choices.len() {
             0 => None,
             1 => Self::choose_single(choices[0]),
-            2.. => Self::choose_multiple(&choices),
+            // ambiguous queries are resolved with the picker, which starts out with the query already typed in
+            2.. => Picker::new(candidates, query, today).pick(),
         };
 
+        Ok(Self::confirm(response))
+    }
+    fn confirm(response: Option<Entry>) -> Option<Entry> {
         if let Some(entry) = response.as_ref() {
-            println!("{} {}", ARROW.cyan().dim(), entry.name.as_str().dim())
+            eprintln!("{} {}", ARROW.cyan().dim(), entry.name.as_str().dim())
         }
-        println!();
+        eprintln!();
         response
     }
     fn choose_single(choice: &Entry) -> Option<Entry> {
-        println!("{} [y/N]", choice);
+        eprintln!("{} [y/N]", choice);
         enter_clean_input_mode();
         loop {
             if !event::poll(std::time::Duration::from_millis(200)).unwrap_or_else(|err| {
@@ -143,60 +168,6 @@ impl Entries {
             }
         }
     }
-    fn choose_multiple(choices: &[&Entry]) -> Option<Entry> {
-        for (idx, choice) in choices.iter().enumerate() {
-            println!("{}. {}", idx + 1, choice);
-        }
-        enter_clean_input_mode();
-        loop {
-            if !event::poll(std::time::Duration::from_millis(200)).unwrap_or_else(|err| {
-                eprintln!("Warning: Event polling issue: '{}'", err);
-                false
-            }) {
-                continue;
-            }
-            let key = match event::read() {
-                Ok(event::Event::Key(key)) => key,
-                Ok(_) => continue,
-                Err(err) => {
-                    eprintln!("Warning: Event read issue, '{}'", err);
-                    continue;
-                }
-            };
-
-            if key.kind != event::KeyEventKind::Press {
-                continue;
-            }
-
-            match key.code {
-                event::KeyCode::Esc
-                | event::KeyCode::Char('Q')
-                | event::KeyCode::Char('q')
-                | event::KeyCode::Char('N')
-                | event::KeyCode::Char('n') => {
-                    exit_clean_input_mode();
-                    break None;
-                }
-                event::KeyCode::Enter => {
-                    exit_clean_input_mode();
-                    break Some(choices[0].clone());
-                }
-                event::KeyCode::Char(chr) => {
-                    if !chr.is_numeric() {
-                        continue;
-                    }
-                    let Ok(idx) = chr.to_string().parse::<usize>() else {
-                        continue;
-                    };
-                    if let Some(entry) = choices.get(idx - 1) {
-                        exit_clean_input_mode();
-                        break Some((*entry).clone());
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
 }
 
 #[cfg(feature = "twotothree")]
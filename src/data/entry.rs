@@ -1,7 +1,7 @@
 use super::syrtime::{Blocs, SyrDate};
 use color_eyre::{
     Result,
-    eyre::{OptionExt, eyre},
+    eyre::{OptionExt, bail, eyre},
 };
 use crossterm::style::Stylize;
 use itertools::Itertools;
@@ -146,7 +146,8 @@ impl Entry {
     }
 
     pub fn delete(self) -> Result<()> {
-        std::fs::remove_file(self.get_filepath()).map_err(Into::into)
+        std::fs::remove_file(self.get_filepath())?;
+        super::sessions::Sessions::delete(&self.name)
     }
 
     pub fn increase_bloc_duration(&mut self, date: &SyrDate, duration: f64) {
@@ -157,6 +158,29 @@ impl Entry {
         }
     }
 
+    pub fn set_bloc_duration(&mut self, date: &SyrDate, duration: f64) {
+        if duration > 0.0 {
+            self.blocs.insert(*date, duration);
+        } else {
+            self.blocs.remove(date);
+        }
+    }
+
+    /// Adds the signed duration to the time tracked on the date, or sets the time tracked to it, which cannot be negative
+    pub fn update_bloc_duration(&mut self, date: &SyrDate, duration: f64, set: bool) -> Result<()> {
+        if set {
+            if duration < 0.0 {
+                bail!("Failed to set the time tracked, the duration is negative");
+            }
+            self.set_bloc_duration(date, duration);
+        } else if duration < 0.0 {
+            self.decrease_bloc_duration(date, -duration);
+        } else {
+            self.increase_bloc_duration(date, duration);
+        }
+        Ok(())
+    }
+
     pub fn decrease_bloc_duration(&mut self, date: &SyrDate, duration: f64) {
         let mut delete_bloc: bool = false;
         if let Some(val) = self.blocs.get_mut(date) {
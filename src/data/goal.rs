@@ -0,0 +1,88 @@
+use super::{
+    Entries, Entry,
+    syrtime::{Bucket, Granularity, SyrDate, SyrSpan},
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// A number of hours to track on an entry every day, week or month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    /// The name or alias of the entry
+    pub entry: String,
+    pub hours: f64,
+    pub per: Granularity,
+}
+
+impl std::fmt::Display for Goal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}h/{}", self.hours, self.per.name())
+    }
+}
+
+impl Goal {
+    pub fn applies_to(&self, entry: &Entry) -> bool {
+        let query = self.entry.to_uppercase();
+        entry.name == query || entry.aliases.contains(&query)
+    }
+
+    /// Returns the time tracked over the whole period containing the date
+    pub fn progress(&self, entry: &Entry, date: SyrDate) -> Progress {
+        let period = self.per.bucket(date);
+        Progress {
+            seconds: period.duration(&entry.blocs),
+            target: self.hours * 3600.0,
+            period,
+        }
+    }
+
+    /// Returns the time tracked over each period overlapping the span, periods cut short by the span being counted whole
+    pub fn history(&self, entry: &Entry, span: SyrSpan) -> Vec<Progress> {
+        self.per
+            .buckets(span)
+            .into_iter()
+            .map(|bucket| self.progress(entry, bucket.start))
+            .collect()
+    }
+}
+
+/// The time tracked towards a goal over one of its periods
+pub struct Progress {
+    pub period: Bucket,
+    pub seconds: f64,
+    pub target: f64,
+}
+
+impl Progress {
+    pub fn is_met(&self) -> bool {
+        self.seconds >= self.target
+    }
+
+    /// The share of the target reached, capped at 1
+    pub fn fraction(&self) -> f64 {
+        if self.target <= 0.0 {
+            1.0
+        } else {
+            (self.seconds / self.target).min(1.0)
+        }
+    }
+
+    /// The number of seconds left to reach the target
+    pub fn remaining(&self) -> f64 {
+        (self.target - self.seconds).max(0.0)
+    }
+}
+
+/// Pairs each configured goal with its entry, goals whose entry does not exist are skipped with a warning
+pub fn goals_of<'a>(goals: &'a [Goal], entries: &'a Entries) -> Vec<(&'a Goal, &'a Entry)> {
+    goals
+        .iter()
+        .filter_map(|goal| match entries.iter().find(|entry| goal.applies_to(entry)) {
+            Some(entry) => Some((goal, entry)),
+            None => {
+                eprintln!("Warning: No entry is named or aliased '{}', skipping the {} goal", goal.entry, goal);
+                None
+            }
+        })
+        .collect_vec()
+}
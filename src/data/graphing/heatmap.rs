@@ -0,0 +1,227 @@
+use super::{GraphFormat, GraphOptions, Layout, Theme, output_filepath, rgb_translate};
+use crate::{
+    config::Config,
+    data::{
+        Entries,
+        syrtime::{MONTH_NAMES, SyrSpan, TimeFormatting, WeekdayFormatting, week_start},
+    },
+};
+use color_eyre::Result;
+use crossterm::style::{Color as TerminalColor, Stylize};
+use itertools::Itertools;
+use jiff::civil::Date;
+use plotters::{coord::Shift, prelude::*};
+
+/// The number of colored levels, an empty day being drawn with level 0
+const LEVELS: usize = 4;
+
+// dimensions in pixels at 96 DPI
+const CELL: u32 = 16;
+const GAP: u32 = 3;
+const MARGIN: u32 = 20;
+const LEFT: u32 = 60;
+const TOP: u32 = 70;
+const BOTTOM: u32 = 50;
+
+/// The daily totals of a year, laid out in columns of weeks starting on the configured weekday
+struct Heatmap {
+    year: i16,
+    first: Date,
+    /// The hours tracked on each day of the year
+    days: Vec<f64>,
+    max: f64,
+}
+
+impl Heatmap {
+    fn new(entries: &Entries, year: i16) -> Result<Self> {
+        let first = Date::new(year, 1, 1)?;
+        let last = Date::new(year, 12, 31)?;
+        let days = SyrSpan::from_start_and_end(first, last)
+            .into_iter()
+            .map(|date| entries.iter().map(|entry| entry.get_bloc_duration(&date)).sum::<f64>() / 3600.0)
+            .collect_vec();
+        let max = days.iter().copied().fold(0.0, f64::max);
+        Ok(Self { year, first, days, max })
+    }
+
+    /// Returns the column (week) and row (weekday) of the nth day of the year
+    fn cell(&self, idx: usize) -> (usize, usize) {
+        let idx = idx + self.first.weekday().since(week_start()) as usize;
+        (idx / 7, idx % 7)
+    }
+
+    fn nb_weeks(&self) -> usize {
+        self.cell(self.days.len() - 1).0 + 1
+    }
+
+    /// Returns the column of the first day of each month along with its name
+    fn months(&self) -> impl Iterator<Item = (usize, &'static str)> + '_ {
+        MONTH_NAMES.iter().enumerate().filter_map(|(idx, name)| {
+            let date = Date::new(self.year, idx as i8 + 1, 1).ok()?;
+            Some((self.cell(date.day_of_year() as usize - 1).0, &name[..3]))
+        })
+    }
+
+    /// Returns the level of a day, 0 when nothing was tracked
+    fn level(&self, hours: f64) -> usize {
+        if hours <= 0.0 || self.max <= 0.0 {
+            0
+        } else {
+            ((hours / self.max * LEVELS as f64).ceil() as usize).clamp(1, LEVELS)
+        }
+    }
+
+    /// Returns the abbreviated weekday of the first, third and fifth rows, the others being left empty
+    fn row_labels() -> [String; 7] {
+        std::array::from_fn(|row| match row {
+            0 | 2 | 4 => WeekdayFormatting::to_string(&week_start().wrapping_add(row as i64))[..3].to_string(),
+            _ => String::new(),
+        })
+    }
+
+    fn title(&self, subject: Option<&str>) -> String {
+        let total = self.days.iter().sum::<f64>() * 3600.0;
+        match subject {
+            Some(subject) => format!("{} - {} - {}", self.year, subject, total.s_str()),
+            None => format!("{} - {}", self.year, total.s_str()),
+        }
+    }
+}
+
+/// Returns the color of a level, blending the empty color into the heatmap color
+fn level_rgb(theme: &Theme, level: usize) -> (u8, u8, u8) {
+    let (empty, full) = (theme.fine_grid_rgb, theme.heatmap_rgb);
+    let ratio = level as f64 / LEVELS as f64;
+    let blend = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * ratio).round() as u8;
+    (blend(empty.0, full.0), blend(empty.1, full.1), blend(empty.2, full.2))
+}
+
+/// Saves a heatmap of the daily totals of the year, subject being the name of the entry it is restricted to
+pub fn heatmap(entries: Entries, year: i16, subject: Option<&str>, options: &GraphOptions) -> Result<()> {
+    let format = options.format.unwrap_or(Config::get().graph_format);
+    let filename = match subject {
+        Some(subject) => format!("Heatmap_{}_{}.{}", year, subject, format.extension()),
+        None => format!("Heatmap_{}.{}", year, format.extension()),
+    };
+
+    let theme = Theme::named(options.theme.as_deref())?;
+    let heatmap = Heatmap::new(&entries, year)?;
+    let layout = Layout::with_size(
+        options,
+        LEFT + heatmap.nb_weeks() as u32 * (CELL + GAP) + MARGIN,
+        TOP + 7 * (CELL + GAP) + BOTTOM,
+    );
+    let filepath = output_filepath(filename);
+
+    match format {
+        GraphFormat::Png => draw(
+            BitMapBackend::new(&filepath, (layout.width, layout.height)).into_drawing_area(),
+            &heatmap,
+            subject,
+            &layout,
+            &theme,
+        ),
+        GraphFormat::Svg => draw(
+            SVGBackend::new(&filepath, (layout.width, layout.height)).into_drawing_area(),
+            &heatmap,
+            subject,
+            &layout,
+            &theme,
+        ),
+    }
+}
+
+fn draw<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    heatmap: &Heatmap,
+    subject: Option<&str>,
+    layout: &Layout,
+    theme: &Theme,
+) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let fg_rgb = rgb_translate(theme.foreground_rgb);
+    let px = |length: u32| layout.px(length) as i32;
+    let title_font = ("sans-serif", layout.px(20)).into_font().color(&fg_rgb);
+    let label_font = ("sans-serif", layout.px(15)).into_font().color(&fg_rgb);
+    let column_x = |col: i32| px(LEFT) + col * px(CELL + GAP);
+    let square = |x: i32, y: i32, level: usize| {
+        Rectangle::new(
+            [(x, y), (x + px(CELL), y + px(CELL))],
+            rgb_translate(level_rgb(theme, level)).filled(),
+        )
+    };
+
+    root.fill(&rgb_translate(theme.background_rgb))?;
+    root.draw(&Text::new(heatmap.title(subject), (px(MARGIN), px(MARGIN)), title_font))?;
+
+    for (col, name) in heatmap.months() {
+        root.draw(&Text::new(name, (column_x(col as i32), px(TOP) - px(22)), label_font.clone()))?;
+    }
+    for (row, name) in Heatmap::row_labels().into_iter().enumerate() {
+        root.draw(&Text::new(
+            name,
+            (px(MARGIN), px(TOP) + row as i32 * px(CELL + GAP)),
+            label_font.clone(),
+        ))?;
+    }
+
+    for (idx, hours) in heatmap.days.iter().enumerate() {
+        let (col, row) = heatmap.cell(idx);
+        root.draw(&square(
+            column_x(col as i32),
+            px(TOP) + row as i32 * px(CELL + GAP),
+            heatmap.level(*hours),
+        ))?;
+    }
+
+    // legend under the first columns
+    let legend_y = px(TOP) + 7 * px(CELL + GAP) + px(GAP * 3);
+    root.draw(&Text::new("Less", (px(MARGIN), legend_y), label_font.clone()))?;
+    for level in 0..=LEVELS {
+        root.draw(&square(column_x(level as i32), legend_y, level))?;
+    }
+    root.draw(&Text::new("More", (column_x(LEVELS as i32 + 1), legend_y), label_font))?;
+
+    Ok(root.present()?)
+}
+
+/// Prints the heatmap to the terminal, one colored square per day, theme being the name of the theme
+pub fn heatmap_terminal(entries: Entries, year: i16, subject: Option<&str>, theme: Option<&str>) -> Result<()> {
+    let theme = Theme::named(theme)?;
+    let heatmap = Heatmap::new(&entries, year)?;
+    let nb_weeks = heatmap.nb_weeks();
+    let square = |level: usize| {
+        let (r, g, b) = level_rgb(&theme, level);
+        "■".with(TerminalColor::Rgb { r, g, b }).to_string()
+    };
+
+    println!("{}", heatmap.title(subject).bold());
+
+    let mut months = vec![' '; nb_weeks + 3];
+    for (col, name) in heatmap.months() {
+        // skip the label when the previous one would be overwritten
+        if months[col.saturating_sub(1)..col].iter().all(|chr| *chr == ' ') {
+            for (offset, chr) in name.chars().enumerate() {
+                months[col + offset] = chr;
+            }
+        }
+    }
+    println!("    {}", months.into_iter().collect::<String>().trim_end());
+
+    let mut grid = vec![vec![None; nb_weeks]; 7];
+    for (idx, hours) in heatmap.days.iter().enumerate() {
+        let (col, row) = heatmap.cell(idx);
+        grid[row][col] = Some(heatmap.level(*hours));
+    }
+    for (row, label) in grid.into_iter().zip(Heatmap::row_labels()) {
+        let cells: String = row.into_iter().map(|level| level.map(square).unwrap_or(" ".to_string())).collect();
+        println!("{:<3} {}", label, cells);
+    }
+
+    println!();
+    println!("Less {} More", (0..=LEVELS).map(square).collect::<String>());
+
+    Ok(())
+}
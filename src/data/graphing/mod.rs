@@ -0,0 +1,574 @@
+use super::{
+    Entries, Entry,
+    syrtime::{Bucket, Granularity, SyrDate, SyrSpan},
+};
+use crate::config::Config;
+use clap::{ValueEnum, builder::PossibleValue};
+use color_eyre::{Result, eyre::bail};
+use itertools::Itertools;
+use plotters::{coord::Shift, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+mod heatmap;
+mod overlays;
+mod pie;
+mod styles;
+mod terminal;
+mod theme;
+pub use heatmap::{heatmap, heatmap_terminal};
+pub use overlays::OverlayOptions;
+pub use styles::GraphStyle;
+use styles::{AxisStyle, Palette, draw_line, draw_stacked_area, draw_stacked_bar};
+pub use terminal::graph_terminal;
+pub use theme::Theme;
+
+trait GraphMethods {
+    fn get_points(&self, buckets: &[Bucket]) -> Vec<(f64, f64)>;
+}
+
+impl GraphMethods for Entry {
+    fn get_points(&self, buckets: &[Bucket]) -> Vec<(f64, f64)> {
+        buckets
+            .iter()
+            .enumerate()
+            // idx + 1 since we pad our graph and 0 is not used
+            .map(|(idx, bucket)| ((idx + 1) as f64, bucket.duration(&self.blocs) / 3600.0))
+            .collect_vec()
+    }
+}
+
+/// The points shared by every kind of graph, x values start at 1 since 0 is used as padding
+pub(super) struct GraphData {
+    pub granularity: Granularity,
+    /// The label of each day, week or month
+    pub labels: Vec<String>,
+    /// The name of each entry along with its points
+    pub superpoints: Vec<(String, Vec<(f64, f64)>)>,
+    pub sum_points: Vec<(f64, f64)>,
+    pub max_y: f64,
+}
+
+impl GraphData {
+    /// Returns None if no time was tracked within the date span
+    pub fn new(entries: &Entries, date_span: SyrSpan, granularity: Granularity) -> Result<Option<Self>> {
+        let buckets = granularity.buckets(date_span);
+
+        if buckets.len() < 3 {
+            bail!("At minimum, a span of three {}s is required to build a graph", granularity.name());
+        }
+
+        let superpoints: Vec<(String, Vec<(f64, f64)>)> = entries
+            .iter()
+            .map(|entry| (entry.name.clone(), entry.get_points(&buckets)))
+            .collect();
+
+        let mut sum_points: Vec<(f64, f64)> = buckets.iter().enumerate().map(|(idx, _)| ((idx + 1) as f64, 0.0)).collect();
+        for (_, points) in superpoints.iter() {
+            for (idx, point) in points.iter().enumerate() {
+                sum_points[idx].1 += point.1
+            }
+        }
+        let max_y = sum_points
+            .iter()
+            .map(|&(_, a)| a)
+            .max_by(|a, b| a.total_cmp(b))
+            .unwrap_or(6.0)
+            .ceil();
+        if max_y == 0.0 {
+            eprintln!("Warning: No entries found within the given date span, returning early");
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            granularity,
+            labels: buckets.into_iter().map(|bucket| bucket.label).collect(),
+            superpoints,
+            sum_points,
+            max_y,
+        }))
+    }
+
+    /// Returns the x values of the first and last points
+    pub fn x_range(&self) -> (f64, f64) {
+        (1.0, self.labels.len() as f64)
+    }
+
+    /// Returns the interpolated sum line, using the configured interpolation method
+    pub fn interpolated_sum(&self) -> Vec<(f64, f64)> {
+        let config = Config::get();
+        config
+            .graph_interpolation_method
+            .interpolate(self.sum_points.clone(), config.graph_nb_interpolated_points)
+    }
+}
+
+/// The file format graphs are saved as
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GraphFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl ValueEnum for GraphFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Png, Self::Svg]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Png => PossibleValue::new("png"),
+            Self::Svg => PossibleValue::new("svg"),
+        })
+    }
+}
+
+impl GraphFormat {
+    pub(super) fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// Determines how a graph is rendered, values left as None fall back to the configuration
+#[derive(Debug, Default)]
+pub struct GraphOptions {
+    pub format: Option<GraphFormat>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub dpi: Option<u32>,
+    pub style: Option<GraphStyle>,
+    /// The name of the theme
+    pub theme: Option<String>,
+    pub overlays: OverlayOptions,
+}
+
+/// The dimensions of a graph once the options and configuration are resolved
+struct Layout {
+    width: u32,
+    height: u32,
+    /// The factor by which every length is multiplied, dpi / 96
+    scale: f64,
+}
+
+impl Layout {
+    fn new(options: &GraphOptions, nb_points: usize) -> Self {
+        let config = Config::get();
+        let width = match options.width.unwrap_or(config.graph_width) {
+            0 => nb_points as u32 * 100 + 500,
+            width => width,
+        };
+        let height = options.height.unwrap_or(config.graph_height).max(1);
+        Self::with_size(options, width, height)
+    }
+
+    /// Scales the given dimensions, in pixels at 96 DPI, by the configured resolution
+    fn with_size(options: &GraphOptions, width: u32, height: u32) -> Self {
+        let scale = options.dpi.unwrap_or(Config::get().graph_dpi).max(1) as f64 / 96.0;
+        Self {
+            width: (width as f64 * scale).round() as u32,
+            height: (height as f64 * scale).round() as u32,
+            scale,
+        }
+    }
+
+    /// Scales a length in pixels at 96 DPI
+    fn px(&self, length: u32) -> u32 {
+        ((length as f64 * self.scale).round() as u32).max(1)
+    }
+}
+
+/// Returns the path of a new graph file, in the configured output directory if it is valid
+pub(super) fn output_filepath(filename: String) -> PathBuf {
+    let path = Config::get().graph_output_dir.clone();
+    if path.is_empty() {
+        PathBuf::from(filename)
+    } else {
+        let path = PathBuf::from(path);
+        if path.is_dir() {
+            path.join(filename)
+        } else {
+            eprintln!("Warning: Invalid directory, defaulting to current directory");
+            PathBuf::from(filename)
+        }
+    }
+}
+
+pub fn graph(entries: Entries, date_span: SyrSpan, granularity: Granularity, options: &GraphOptions) -> Result<()> {
+    let format = options.format.unwrap_or(Config::get().graph_format);
+    let filename = format!(
+        "Graph_from_{}_to_{}.{}",
+        SyrDate::from(date_span.start).as_string_with_formatting('-'),
+        SyrDate::from(date_span.end).as_string_with_formatting('-'),
+        format.extension()
+    );
+
+    let theme = Theme::named(options.theme.as_deref())?;
+
+    let style = match options.style.unwrap_or(Config::get().graph_style) {
+        GraphStyle::Pie => return pie::graph_pie(entries, date_span, &output_filepath(filename), format, options, &theme),
+        GraphStyle::Line => AxisStyle::Line,
+        GraphStyle::StackedBar => AxisStyle::StackedBar,
+        GraphStyle::StackedArea => AxisStyle::StackedArea,
+    };
+
+    let Some(data) = GraphData::new(&entries, date_span, granularity)? else {
+        return Ok(());
+    };
+
+    let layout = Layout::new(options, data.labels.len());
+    let filepath = output_filepath(filename);
+
+    match format {
+        GraphFormat::Png => draw(
+            BitMapBackend::new(&filepath, (layout.width, layout.height)).into_drawing_area(),
+            data,
+            &layout,
+            &theme,
+            style,
+            &options.overlays,
+        ),
+        GraphFormat::Svg => draw(
+            SVGBackend::new(&filepath, (layout.width, layout.height)).into_drawing_area(),
+            data,
+            &layout,
+            &theme,
+            style,
+            &options.overlays,
+        ),
+    }
+}
+
+fn draw<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    data: GraphData,
+    layout: &Layout,
+    theme: &Theme,
+    style: AxisStyle,
+    overlays: &OverlayOptions,
+) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let bg_rgb = rgb_translate(theme.background_rgb);
+    let fg_rgb = rgb_translate(theme.foreground_rgb);
+    let coarse_grid_rgb = rgb_translate(theme.coarse_grid_rgb);
+    let fine_grid_rgb = rgb_translate(theme.fine_grid_rgb);
+    let stroke_width = layout.px(2);
+
+    let palette = Palette::new(theme, &data, layout.px(Config::get().graph_marker_size), stroke_width);
+    let labels = &data.labels;
+
+    root.fill::<RGBColor>(&bg_rgb)?;
+
+    let mut ctx = ChartBuilder::on(&root)
+        .margin_top(layout.px(30))
+        .margin_right(layout.px(30))
+        .set_label_area_size(LabelAreaPosition::Left, layout.px(50))
+        .set_label_area_size(LabelAreaPosition::Bottom, layout.px(50))
+        // ignore 0 and pad by 2 to the right
+        .build_cartesian_2d(0_f64..(labels.len() + 2) as f64, 0_f64..data.max_y)?;
+
+    ctx.configure_mesh()
+        .axis_style(ShapeStyle {
+            color: fg_rgb.to_rgba(),
+            filled: true,
+            stroke_width,
+        })
+        .label_style(("sans-serif", layout.px(20)).with_color(fg_rgb.to_rgba()))
+        .x_label_formatter(&|v| {
+            let v_idx = *v as usize;
+            if v_idx <= labels.len() && v_idx > 0 {
+                labels[v_idx - 1].clone()
+            } else {
+                String::with_capacity(0)
+            }
+        })
+        // due to padding (1 on the left, 2 on the right)
+        .x_labels(labels.len() + 3)
+        .bold_line_style(coarse_grid_rgb.to_rgba().stroke_width(stroke_width))
+        .light_line_style(fine_grid_rgb.to_rgba().stroke_width(layout.px(1)))
+        .draw()?;
+
+    match style {
+        AxisStyle::Line => draw_line(&mut ctx, &data, &palette)?,
+        AxisStyle::StackedBar => draw_stacked_bar(&mut ctx, &data, &palette)?,
+        AxisStyle::StackedArea => draw_stacked_area(&mut ctx, &data, &palette)?,
+    }
+    overlays::draw_overlays(&mut ctx, &data, &overlays::overlays(&data, overlays), &palette)?;
+
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .border_style(fg_rgb)
+        .margin(layout.px(15))
+        .label_font(("sans-serif", layout.px(15) as f64).with_color(fg_rgb))
+        .draw()?;
+
+    Ok(root.present()?)
+}
+
+pub(super) fn rgb_translate(rgb: (u8, u8, u8)) -> RGBColor {
+    RGBColor(rgb.0, rgb.1, rgb.2)
+}
+
+pub mod interpolation {
+    use itertools::Itertools;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    pub(crate) enum InterpolationMethod {
+        Linear,
+        Makima,
+        /// Piecewise cubic Hermite, shape-preserving so it never overshoots the data
+        Pchip,
+        /// Natural cubic spline, with a second derivative of zero at both ends
+        NaturalCubic,
+        /// Holds each value until the next point
+        Step,
+    }
+
+    impl InterpolationMethod {
+        pub(super) fn interpolate(&self, points: Vec<(f64, f64)>, nb_points: usize) -> Vec<(f64, f64)> {
+            match self {
+                Self::Linear => linear(points, nb_points),
+                Self::Makima => makima(points, nb_points),
+                Self::Pchip => pchip(points, nb_points),
+                Self::NaturalCubic => natural_cubic(points, nb_points),
+                Self::Step => step(points, nb_points),
+            }
+        }
+    }
+
+    /// Evaluates each segment nb_points times, starting at its first point, eval being given the index of the segment and x
+    fn sample(points: &[(f64, f64)], nb_points: usize, eval: impl Fn(usize, f64) -> f64) -> Vec<(f64, f64)> {
+        let nb_points = nb_points.max(1);
+        let mut sampled: Vec<(f64, f64)> = points
+            .iter()
+            .tuple_windows()
+            .enumerate()
+            .flat_map(|(i, (&(x_i, _), &(x_ip1, _)))| {
+                let step_size = (x_ip1 - x_i) / nb_points as f64;
+                (0..nb_points).map(move |k| x_i + step_size * k as f64).map(move |x| (i, x))
+            })
+            .map(|(i, x)| (x, eval(i, x)))
+            .collect();
+        sampled.extend(points.last());
+        sampled
+    }
+
+    /// Evaluates the cubic Hermite spline going through the points with the given slopes, clamped to zero
+    fn hermite(points: &[(f64, f64)], slopes: &[f64], nb_points: usize) -> Vec<(f64, f64)> {
+        sample(points, nb_points, |i, x| {
+            let ((x_i, y_i), (x_ip1, y_ip1)) = (points[i], points[i + 1]);
+            let h = x_ip1 - x_i;
+            let t = (x - x_i) / h;
+            let (t2, t3) = (t * t, t * t * t);
+            let y = (2.0 * t3 - 3.0 * t2 + 1.0) * y_i
+                + (t3 - 2.0 * t2 + t) * h * slopes[i]
+                + (-2.0 * t3 + 3.0 * t2) * y_ip1
+                + (t3 - t2) * h * slopes[i + 1];
+            y.max(0.0)
+        })
+    }
+
+    /// Slopes between each point
+    fn secants(points: &[(f64, f64)]) -> Vec<f64> {
+        points
+            .iter()
+            .tuple_windows()
+            .map(|(&(x_i, y_i), &(x_ip1, y_ip1))| (y_ip1 - y_i) / (x_ip1 - x_i))
+            .collect()
+    }
+
+    // plotters.rs has a lineseries options but I dislike it as the width is not consistent depending on the slope
+    // this method gives us a more consistent line width
+    pub(super) fn linear(points: Vec<(f64, f64)>, nb_points: usize) -> Vec<(f64, f64)> {
+        sample(&points, nb_points, |i, x| {
+            let ((x_i, y_i), (x_ip1, y_ip1)) = (points[i], points[i + 1]);
+            (y_i * (x_ip1 - x) + y_ip1 * (x - x_i)) / (x_ip1 - x_i)
+        })
+    }
+
+    pub(super) fn makima(points: Vec<(f64, f64)>, nb_points: usize) -> Vec<(f64, f64)> {
+        if points.len() < 5 {
+            eprintln!(
+                "Warning: A minimum of 5 points are required to use m-Akima interpolation, got {}",
+                points.len()
+            );
+            return linear(points, nb_points);
+        }
+        let n = points.len() - 1;
+        let m = secants(&points);
+
+        // spline slopes
+        let mut s: Vec<f64> = Vec::new();
+        // deals with the two first spline slopes
+        s.push(m[0]);
+        s.push((m[0] + m[1]) / 2.0);
+        for i in 2..points.len() - 2 {
+            s.push({
+                let w_1 = (m[i + 1] - m[i]).abs() + (m[i + 1] + m[i]).abs() / 2.0;
+                let w_2 = (m[i - 1] - m[i - 2]).abs() + (m[i - 1] + m[i - 2]).abs() / 2.0;
+                if w_1 + w_2 == 0.0 {
+                    (m[i - 1] + m[i]) / 2.0
+                } else {
+                    (w_1 / (w_1 + w_2)) * m[i - 1] + (w_2 / (w_1 + w_2)) * m[i]
+                }
+            });
+        }
+        // deals with the last two spline slopes
+        s.push((m[n - 3] + m[n - 2]) / 2.0);
+        s.push(m[n - 1]);
+
+        hermite(&points, &s, nb_points)
+    }
+
+    /// Fritsch-Carlson slopes, flat at local extrema so that each segment stays between its two points
+    pub(super) fn pchip(points: Vec<(f64, f64)>, nb_points: usize) -> Vec<(f64, f64)> {
+        if points.len() < 3 {
+            return linear(points, nb_points);
+        }
+        let n = points.len() - 1;
+        let h: Vec<f64> = points.iter().tuple_windows().map(|((x_i, _), (x_ip1, _))| x_ip1 - x_i).collect();
+        let m = secants(&points);
+
+        // three-point estimate at the ends, limited so it cannot overshoot
+        let end_slope = |h_0: f64, h_1: f64, m_0: f64, m_1: f64| {
+            let s = ((2.0 * h_0 + h_1) * m_0 - h_0 * m_1) / (h_0 + h_1);
+            if s.signum() != m_0.signum() || m_0 == 0.0 {
+                0.0
+            } else if m_0.signum() != m_1.signum() && s.abs() > 3.0 * m_0.abs() {
+                3.0 * m_0
+            } else {
+                s
+            }
+        };
+
+        let mut s = vec![end_slope(h[0], h[1], m[0], m[1])];
+        for i in 1..n {
+            s.push(if m[i - 1] * m[i] <= 0.0 {
+                0.0
+            } else {
+                let w_1 = 2.0 * h[i] + h[i - 1];
+                let w_2 = h[i] + 2.0 * h[i - 1];
+                (w_1 + w_2) / (w_1 / m[i - 1] + w_2 / m[i])
+            });
+        }
+        s.push(end_slope(h[n - 1], h[n - 2], m[n - 1], m[n - 2]));
+
+        hermite(&points, &s, nb_points)
+    }
+
+    /// Solves for the second derivatives with the Thomas algorithm, the spline being clamped to zero where it dips below
+    pub(super) fn natural_cubic(points: Vec<(f64, f64)>, nb_points: usize) -> Vec<(f64, f64)> {
+        if points.len() < 3 {
+            return linear(points, nb_points);
+        }
+        let n = points.len() - 1;
+        let h: Vec<f64> = points.iter().tuple_windows().map(|((x_i, _), (x_ip1, _))| x_ip1 - x_i).collect();
+        let m = secants(&points);
+
+        // second derivatives, zero at both ends
+        let mut c = vec![0.0; n + 1];
+        let mut upper = vec![0.0; n];
+        let mut rhs = vec![0.0; n];
+        for i in 1..n {
+            let diagonal = 2.0 * (h[i - 1] + h[i]) - h[i - 1] * upper[i - 1];
+            upper[i] = h[i] / diagonal;
+            rhs[i] = (6.0 * (m[i] - m[i - 1]) - h[i - 1] * rhs[i - 1]) / diagonal;
+        }
+        for i in (1..n).rev() {
+            c[i] = rhs[i] - upper[i] * c[i + 1];
+        }
+
+        let mut s: Vec<f64> = (0..n).map(|i| m[i] - h[i] * (2.0 * c[i] + c[i + 1]) / 6.0).collect();
+        s.push(m[n - 1] + h[n - 1] * (c[n - 1] + 2.0 * c[n]) / 6.0);
+
+        hermite(&points, &s, nb_points)
+    }
+
+    /// Draws the jump to the next value as a vertical line so that the steps stay connected
+    pub(super) fn step(points: Vec<(f64, f64)>, nb_points: usize) -> Vec<(f64, f64)> {
+        let nb_points = nb_points.max(1);
+        let mut sampled: Vec<(f64, f64)> = points
+            .iter()
+            .tuple_windows()
+            .flat_map(|(&(x_i, y_i), &(x_ip1, y_ip1))| {
+                let horizontal = (0..nb_points).map(move |k| (x_i + (x_ip1 - x_i) * k as f64 / nb_points as f64, y_i));
+                let vertical = (0..nb_points).map(move |k| (x_ip1, y_i + (y_ip1 - y_i) * k as f64 / nb_points as f64));
+                horizontal.chain(vertical)
+            })
+            .collect();
+        sampled.extend(points.last());
+        sampled
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const METHODS: [InterpolationMethod; 5] = [
+            InterpolationMethod::Linear,
+            InterpolationMethod::Makima,
+            InterpolationMethod::Pchip,
+            InterpolationMethod::NaturalCubic,
+            InterpolationMethod::Step,
+        ];
+
+        /// Daily hours with idle days and spikes, the kind of data that makes splines overshoot below zero
+        fn spiky() -> Vec<(f64, f64)> {
+            [0.0, 0.0, 6.5, 0.0, 0.2, 8.0, 7.5, 0.0, 0.0, 0.0, 3.0, 0.1]
+                .into_iter()
+                .enumerate()
+                .map(|(idx, y)| ((idx + 1) as f64, y))
+                .collect()
+        }
+
+        #[test]
+        fn passes_through_the_points() {
+            for method in METHODS {
+                for points in [spiky(), vec![(1.0, 2.0), (2.0, 0.0), (3.0, 4.0)]] {
+                    let interpolated = method.interpolate(points.clone(), 20);
+                    for (x, y) in points {
+                        assert!(
+                            interpolated
+                                .iter()
+                                .any(|(x_i, y_i)| (x_i - x).abs() < 1e-9 && (y_i - y).abs() < 1e-9),
+                            "{method:?} misses ({x}, {y})"
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn stays_non_negative() {
+            for method in METHODS {
+                let interpolated = method.interpolate(spiky(), 50);
+                assert!(interpolated.iter().all(|(_, y)| *y >= 0.0), "{method:?} dips below zero");
+            }
+        }
+
+        #[test]
+        fn pchip_stays_between_neighbours() {
+            let points = spiky();
+            let interpolated = InterpolationMethod::Pchip.interpolate(points.clone(), 50);
+            for (x, y) in interpolated {
+                let i = (x.floor() as usize - 1).min(points.len() - 2);
+                let (low, high) = (points[i].1.min(points[i + 1].1), points[i].1.max(points[i + 1].1));
+                assert!(low - 1e-9 <= y && y <= high + 1e-9, "({x}, {y}) overshoots");
+            }
+        }
+
+        #[test]
+        fn natural_cubic_matches_known_spline() {
+            // the natural spline through (0, 0), (1, 1), (2, 0) peaks at 1 with s(0.5) = 11/16
+            let interpolated = natural_cubic(vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)], 2);
+            let (_, y) = interpolated.iter().find(|(x, _)| (x - 0.5).abs() < 1e-9).unwrap();
+            assert!((y - 11.0 / 16.0).abs() < 1e-9, "{y}");
+        }
+    }
+}
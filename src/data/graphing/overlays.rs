@@ -0,0 +1,199 @@
+use super::{GraphData, styles::Palette};
+use color_eyre::Result;
+use itertools::Itertools;
+use plotters::{
+    coord::{cartesian::Cartesian2d, types::RangedCoordf64},
+    prelude::*,
+};
+
+/// Determines the lines drawn over a graph, the total always being included when enabled
+#[derive(Debug, Default, Clone)]
+pub struct OverlayOptions {
+    /// The number of points averaged, None disables the moving average
+    pub moving_average: Option<usize>,
+    pub trend: bool,
+    /// The names of the entries that also get overlays
+    pub entries: Vec<String>,
+}
+
+impl OverlayOptions {
+    fn is_empty(&self) -> bool {
+        self.moving_average.is_none() && !self.trend
+    }
+}
+
+/// The least-squares line fitted through a series of points
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Trend {
+    pub slope: f64,
+    pub intercept: f64,
+}
+
+impl Trend {
+    /// Returns None if there are less than two points
+    pub fn fit(points: &[(f64, f64)]) -> Option<Self> {
+        if points.len() < 2 {
+            return None;
+        }
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        if variance == 0.0 {
+            return None;
+        }
+        let slope = covariance / variance;
+        Some(Self {
+            slope,
+            intercept: mean_y - slope * mean_x,
+        })
+    }
+
+    fn at(&self, x: f64) -> f64 {
+        self.slope * x + self.intercept
+    }
+
+    /// Returns the segment of the line between x0 and x1, cut where it would go below zero
+    pub fn segment(&self, x0: f64, x1: f64) -> Option<[(f64, f64); 2]> {
+        let (y0, y1) = (self.at(x0), self.at(x1));
+        if y0 < 0.0 && y1 < 0.0 {
+            return None;
+        }
+        let root = -self.intercept / self.slope;
+        let start = if y0 < 0.0 { (root, 0.0) } else { (x0, y0) };
+        let end = if y1 < 0.0 { (root, 0.0) } else { (x1, y1) };
+        Some([start, end])
+    }
+}
+
+/// Returns the trailing moving average of the points, the first ones averaging over the points available so far
+pub(super) fn moving_average(points: &[(f64, f64)], window: usize) -> Vec<(f64, f64)> {
+    let window = window.max(1);
+    points
+        .iter()
+        .enumerate()
+        .map(|(idx, (x, _))| {
+            let start = (idx + 1).saturating_sub(window);
+            let values = &points[start..=idx];
+            (*x, values.iter().map(|(_, y)| y).sum::<f64>() / values.len() as f64)
+        })
+        .collect()
+}
+
+/// The moving average and trend line of the total or of a single entry
+pub(super) struct Overlay {
+    /// None for the total
+    pub name: Option<String>,
+    pub moving_average: Option<Vec<(f64, f64)>>,
+    pub trend: Option<Trend>,
+}
+
+impl Overlay {
+    pub fn title(&self) -> &str {
+        self.name.as_deref().unwrap_or("Total")
+    }
+}
+
+/// Returns the overlays of the total followed by those of the selected entries
+pub(super) fn overlays(data: &GraphData, options: &OverlayOptions) -> Vec<Overlay> {
+    if options.is_empty() {
+        return Vec::new();
+    }
+    let build = |name: Option<String>, points: &[(f64, f64)]| Overlay {
+        name,
+        moving_average: options.moving_average.map(|window| moving_average(points, window)),
+        trend: options.trend.then(|| Trend::fit(points)).flatten(),
+    };
+
+    std::iter::once(build(None, &data.sum_points))
+        .chain(
+            data.superpoints
+                .iter()
+                .filter(|(name, _)| options.entries.contains(name))
+                .map(|(name, points)| build(Some(name.clone()), points)),
+        )
+        .collect_vec()
+}
+
+pub(super) fn draw_overlays<DB: DrawingBackend>(
+    ctx: &mut ChartContext<'_, DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    data: &GraphData,
+    overlays: &[Overlay],
+    palette: &Palette,
+) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let stroke_width = palette.stroke_width;
+    let (first, last) = data.x_range();
+
+    for overlay in overlays {
+        let color = match overlay.name.as_deref() {
+            Some(name) => palette.color_of(name).unwrap_or(palette.foreground),
+            None => palette.sum_line,
+        };
+        let style = color.stroke_width(stroke_width);
+
+        if let Some(points) = overlay.moving_average.as_ref() {
+            // thicker than the interpolated sum so that both can be told apart
+            let bold = color.stroke_width(stroke_width * 2);
+            ctx.draw_series(LineSeries::new(points.iter().copied(), bold))?
+                .label(format!("{}, moving average", overlay.title()))
+                .legend(move |(x, y)| PathElement::new(vec![(x - 8, y), (x + 8, y)], bold));
+        }
+
+        if let Some(trend) = overlay.trend
+            && let Some(segment) = trend.segment(first, last)
+        {
+            ctx.draw_series(DashedLineSeries::new(
+                segment,
+                palette.marker_size as i32,
+                palette.marker_size as i32,
+                style,
+            ))?
+            .label(format!(
+                "{}, trend {:+.2}h/{}",
+                overlay.title(),
+                trend.slope,
+                data.granularity.name()
+            ))
+            .legend(move |(x, y)| PathElement::new(vec![(x - 8, y), (x - 2, y)], style));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trend() {
+        let trend = Trend::fit(&[(1.0, 1.0), (2.0, 3.0), (3.0, 5.0)]).unwrap();
+        assert_eq!((trend.slope, trend.intercept), (2.0, -1.0));
+        // cut where the line crosses zero
+        assert_eq!(trend.segment(0.0, 3.0), Some([(0.5, 0.0), (3.0, 5.0)]));
+        assert_eq!(trend.segment(-2.0, 0.0), None);
+
+        assert!(Trend::fit(&[]).is_none());
+        assert!(Trend::fit(&[(1.0, 4.0)]).is_none());
+        // no variance along x
+        assert!(Trend::fit(&[(2.0, 1.0), (2.0, 3.0)]).is_none());
+        // no variance along y
+        let flat = Trend::fit(&[(1.0, 2.0), (2.0, 2.0), (3.0, 2.0)]).unwrap();
+        assert_eq!((flat.slope, flat.intercept), (0.0, 2.0));
+        assert_eq!(flat.segment(1.0, 3.0), Some([(1.0, 2.0), (3.0, 2.0)]));
+    }
+
+    #[test]
+    fn moving_averages() {
+        let points = [(1.0, 2.0), (2.0, 4.0), (3.0, 6.0), (4.0, 0.0)];
+        assert_eq!(moving_average(&points, 2), [(1.0, 2.0), (2.0, 3.0), (3.0, 5.0), (4.0, 3.0)]);
+        // a window larger than the series averages over the points available so far
+        assert_eq!(moving_average(&points, 10), [(1.0, 2.0), (2.0, 3.0), (3.0, 4.0), (4.0, 3.0)]);
+        assert_eq!(moving_average(&points[..1], 7), [(1.0, 2.0)]);
+        assert_eq!(moving_average(&points, 0), points);
+        assert!(moving_average(&[], 3).is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use super::{GraphFormat, GraphOptions, Layout, Theme, rgb_translate};
+use crate::{
+    config::Config,
+    data::{
+        Entries,
+        syrtime::{Bucket, SyrSpan, TimeFormatting},
+    },
+};
+use color_eyre::Result;
+use itertools::Itertools;
+use plotters::{coord::Shift, prelude::*};
+use std::path::Path;
+
+/// The share of the time tracked by each entry, smaller shares being grouped together
+struct Slices {
+    labels: Vec<String>,
+    hours: Vec<f64>,
+    colors: Vec<RGBColor>,
+    title: String,
+}
+
+impl Slices {
+    /// Returns None if no time was tracked within the date span
+    fn new(entries: &Entries, date_span: SyrSpan, theme: &Theme) -> Option<Self> {
+        let config = Config::get();
+        let span = Bucket {
+            start: date_span.start.into(),
+            end: date_span.end.into(),
+            label: String::new(),
+        };
+        let totals = entries
+            .iter()
+            .filter_map(|entry| Some((entry.name.as_str(), span.duration_opt(&entry.blocs)? / 3600.0)))
+            .filter(|(_, hours)| *hours > 0.0)
+            .sorted_by(|(_, a), (_, b)| b.total_cmp(a))
+            .collect_vec();
+        let total: f64 = totals.iter().map(|(_, hours)| hours).sum();
+        if total == 0.0 {
+            eprintln!("Warning: No entries found within the given date span, returning early");
+            return None;
+        }
+
+        // a single small entry is kept as is, there is nothing to group it with
+        let (kept, others): (Vec<_>, Vec<_>) = totals
+            .into_iter()
+            .partition(|(_, hours)| hours / total * 100.0 >= config.graph_pie_threshold);
+        let (kept, others) = match others.len() {
+            0 | 1 => (kept.into_iter().chain(others).collect_vec(), Vec::new()),
+            _ => (kept, others),
+        };
+
+        let mut slices = Self {
+            labels: Vec::new(),
+            hours: Vec::new(),
+            colors: Vec::new(),
+            title: format!("{} - {} : {}", span.start, span.end, (total * 3600.0).s_str()),
+        };
+        // slices sharing a color are blended further and further into the background
+        let background = rgb_translate(theme.background_rgb);
+        let markers = theme.markers(kept.iter().map(|(name, _)| *name));
+        for ((name, hours), (rgb, pass)) in kept.into_iter().zip(markers) {
+            let shade = (pass as f64 * 0.3).min(0.6);
+            let color = rgb_translate(rgb);
+            let blend = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * shade).round() as u8;
+            slices.labels.push(format!("{} ({:.1}h)", name, hours));
+            slices.hours.push(hours);
+            slices.colors.push(RGBColor(
+                blend(color.0, background.0),
+                blend(color.1, background.1),
+                blend(color.2, background.2),
+            ));
+        }
+        if !others.is_empty() {
+            let hours: f64 = others.iter().map(|(_, hours)| hours).sum();
+            slices.labels.push(format!("Other ({:.1}h)", hours));
+            slices.hours.push(hours);
+            slices.colors.push(rgb_translate(theme.coarse_grid_rgb));
+        }
+        Some(slices)
+    }
+}
+
+/// Saves a pie chart of the time tracked by each entry over the whole date span
+pub(super) fn graph_pie(
+    entries: Entries,
+    date_span: SyrSpan,
+    filepath: &Path,
+    format: GraphFormat,
+    options: &GraphOptions,
+    theme: &Theme,
+) -> Result<()> {
+    let Some(slices) = Slices::new(&entries, date_span, theme) else {
+        return Ok(());
+    };
+
+    let config = Config::get();
+    let height = options.height.unwrap_or(config.graph_height).max(1);
+    let width = match options.width.unwrap_or(config.graph_width) {
+        0 => height * 3 / 2,
+        width => width,
+    };
+    let layout = Layout::with_size(options, width, height);
+
+    match format {
+        GraphFormat::Png => draw(
+            BitMapBackend::new(filepath, (layout.width, layout.height)).into_drawing_area(),
+            &slices,
+            &layout,
+            theme,
+        ),
+        GraphFormat::Svg => draw(
+            SVGBackend::new(filepath, (layout.width, layout.height)).into_drawing_area(),
+            &slices,
+            &layout,
+            theme,
+        ),
+    }
+}
+
+fn draw<DB: DrawingBackend>(root: DrawingArea<DB, Shift>, slices: &Slices, layout: &Layout, theme: &Theme) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let bg_rgb = rgb_translate(theme.background_rgb);
+    let fg_rgb = rgb_translate(theme.foreground_rgb);
+
+    root.fill(&bg_rgb)?;
+    root.draw(&Text::new(
+        slices.title.as_str(),
+        (layout.px(20) as i32, layout.px(20) as i32),
+        ("sans-serif", layout.px(20)).into_font().color(&fg_rgb),
+    ))?;
+
+    let center = (layout.width as i32 / 2, layout.height as i32 / 2 + layout.px(15) as i32);
+    // leaves room for the labels around the pie
+    let radius = (layout.width.min(layout.height) as f64 / 2.0 - layout.px(90) as f64).max(1.0);
+
+    let mut pie = Pie::new(&center, &radius, &slices.hours, &slices.colors, &slices.labels);
+    pie.start_angle(-90.0);
+    pie.label_style(("sans-serif", layout.px(18)).into_font().color(&fg_rgb));
+    pie.label_offset(radius * 0.1);
+    pie.percentages(("sans-serif", layout.px(16)).into_font().color(&bg_rgb));
+    pie.donut_hole(radius * Config::get().graph_pie_hole.clamp(0.0, 0.95));
+    root.draw(&pie)?;
+
+    Ok(root.present()?)
+}
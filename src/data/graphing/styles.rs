@@ -0,0 +1,261 @@
+use super::{GraphData, Theme, rgb_translate};
+use clap::{ValueEnum, builder::PossibleValue};
+use color_eyre::Result;
+use itertools::Itertools;
+use plotters::{
+    coord::{cartesian::Cartesian2d, types::RangedCoordf64},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+type Chart<'a, DB> = ChartContext<'a, DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>;
+
+/// The way entries are drawn on a graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GraphStyle {
+    /// Markers for each entry and an interpolated line for the sum
+    #[default]
+    Line,
+    /// One bar per day, split into the contribution of each entry
+    StackedBar,
+    /// Areas stacked on top of each other, the top one following the sum
+    StackedArea,
+    /// The share of each entry over the whole span, drawn as a pie or donut
+    Pie,
+}
+
+impl ValueEnum for GraphStyle {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Line, Self::StackedBar, Self::StackedArea, Self::Pie]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Line => PossibleValue::new("line"),
+            Self::StackedBar => PossibleValue::new("stacked-bar").alias("bar"),
+            Self::StackedArea => PossibleValue::new("stacked-area").alias("area"),
+            Self::Pie => PossibleValue::new("pie").alias("donut"),
+        })
+    }
+}
+
+/// The styles drawn on axes, that is every style but the pie
+#[derive(Debug, Clone, Copy)]
+pub(super) enum AxisStyle {
+    Line,
+    StackedBar,
+    StackedArea,
+}
+
+/// Entries given the same color are told apart by their pattern
+#[derive(Clone, Copy)]
+enum Pattern {
+    Solid,
+    Hatched,
+    Outlined,
+}
+
+/// The colors and sizes shared by every style
+pub(super) struct Palette {
+    pub background: RGBColor,
+    pub foreground: RGBColor,
+    pub sum_line: RGBColor,
+    pub marker_size: u32,
+    pub stroke_width: u32,
+    /// The color of each active entry along with its pass, the pass increasing with each earlier entry given the same color
+    markers: HashMap<String, (RGBColor, usize)>,
+}
+
+impl Palette {
+    pub fn new(theme: &Theme, data: &GraphData, marker_size: u32, stroke_width: u32) -> Self {
+        let names = active(data).map(|(name, _)| name.as_str()).collect_vec();
+        let markers = theme.markers(names.iter().copied());
+        Self {
+            background: rgb_translate(theme.background_rgb),
+            foreground: rgb_translate(theme.foreground_rgb),
+            sum_line: rgb_translate(theme.sum_line_rgb),
+            marker_size,
+            stroke_width,
+            markers: names
+                .into_iter()
+                .zip(markers)
+                .map(|(name, (rgb, pass))| (name.to_string(), (rgb_translate(rgb), pass)))
+                .collect(),
+        }
+    }
+
+    /// Returns the color of the entry along with its pass, the foreground for entries that are not drawn
+    fn pick(&self, name: &str) -> (RGBColor, usize) {
+        self.markers.get(name).copied().unwrap_or((self.foreground, 0))
+    }
+
+    /// Returns the color of the entry, as drawn by every style but the pie chart
+    pub fn color_of(&self, name: &str) -> Option<RGBColor> {
+        self.markers.get(name).map(|(color, _)| *color)
+    }
+
+    fn pattern(&self, name: &str) -> (RGBColor, Pattern) {
+        let (color, pass) = self.pick(name);
+        let pattern = match pass % 3 {
+            0 => Pattern::Solid,
+            1 => Pattern::Hatched,
+            _ => Pattern::Outlined,
+        };
+        (color, pattern)
+    }
+
+    /// The style of the lines drawn over hatched series
+    fn hatching(&self, pattern: Pattern) -> Option<ShapeStyle> {
+        match pattern {
+            Pattern::Hatched => Some(self.background.stroke_width(self.stroke_width)),
+            _ => None,
+        }
+    }
+
+    fn fill(&self, color: RGBColor, pattern: Pattern) -> ShapeStyle {
+        match pattern {
+            Pattern::Solid | Pattern::Hatched => color.filled(),
+            Pattern::Outlined => color.stroke_width(self.stroke_width),
+        }
+    }
+}
+
+/// Returns the entries that tracked time within the span, in the order of the entries
+fn active(data: &GraphData) -> impl Iterator<Item = &(String, Vec<(f64, f64)>)> {
+    data.superpoints.iter().filter(|(_, points)| points.iter().any(|(_, y)| *y != 0.0))
+}
+
+pub(super) fn draw_line<DB: DrawingBackend>(ctx: &mut Chart<'_, DB>, data: &GraphData, palette: &Palette) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let stroke_width = palette.stroke_width;
+    let marker_size = palette.marker_size;
+
+    ctx.draw_series(
+        data.interpolated_sum()
+            .into_iter()
+            .map(|coord| Circle::new(coord, 0, palette.sum_line.stroke_width(stroke_width))),
+    )?;
+
+    for (name, points) in active(data) {
+        let (color, pass) = palette.pick(name);
+        let style = color.stroke_width(stroke_width);
+        let points = points.iter().copied().filter(|(_, y)| *y != 0.0);
+        match pass % 3 {
+            0 => ctx
+                .draw_series(points.map(|coord| Circle::new(coord, marker_size, style)))?
+                .label(name)
+                .legend(move |coord| Circle::new(coord, marker_size, style)),
+            1 => ctx
+                .draw_series(points.map(|coord| TriangleMarker::new(coord, marker_size, style)))?
+                .label(name)
+                .legend(move |coord| TriangleMarker::new(coord, marker_size, style)),
+            _ => ctx
+                .draw_series(points.map(|coord| Cross::new(coord, marker_size, style)))?
+                .label(name)
+                .legend(move |coord| Cross::new(coord, marker_size, style)),
+        };
+    }
+    Ok(())
+}
+
+pub(super) fn draw_stacked_bar<DB: DrawingBackend>(ctx: &mut Chart<'_, DB>, data: &GraphData, palette: &Palette) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    const HALF_WIDTH: f64 = 0.35;
+    let mut bottoms = vec![0.0; data.labels.len()];
+
+    for (name, points) in active(data) {
+        let (color, pattern) = palette.pattern(name);
+        let style = palette.fill(color, pattern);
+        let hatching = palette.hatching(pattern);
+        let bars = points
+            .iter()
+            .zip(bottoms.iter_mut())
+            .filter(|((_, y), _)| *y != 0.0)
+            .map(|(&(x, y), bottom)| {
+                let bar = [(x - HALF_WIDTH, *bottom), (x + HALF_WIDTH, *bottom + y)];
+                *bottom += y;
+                bar
+            })
+            .collect::<Vec<_>>();
+
+        ctx.draw_series(bars.iter().map(|corners| Rectangle::new(*corners, style)))?
+            .label(name)
+            .legend(move |coord| legend_swatch(coord, style, hatching));
+
+        if let Some(hatching) = hatching {
+            let hatching = bars.iter().flat_map(|[(x0, y0), (x1, y1)]| {
+                (1..6).map(move |step| {
+                    let x = x0 + (x1 - x0) * step as f64 / 6.0;
+                    PathElement::new(vec![(x, *y0), (x, *y1)], hatching)
+                })
+            });
+            ctx.draw_series(hatching)?;
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn draw_stacked_area<DB: DrawingBackend>(ctx: &mut Chart<'_, DB>, data: &GraphData, palette: &Palette) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let mut lower: Vec<(f64, f64)> = data.sum_points.iter().map(|(x, _)| (*x, 0.0)).collect();
+
+    for (name, points) in active(data) {
+        let (color, pattern) = palette.pattern(name);
+        let style = palette.fill(color, pattern);
+        let hatching = palette.hatching(pattern);
+        let upper: Vec<(f64, f64)> = lower
+            .iter()
+            .zip(points.iter())
+            .map(|((x, bottom), (_, y))| (*x, bottom + y))
+            .collect();
+        let outline: Vec<(f64, f64)> = upper.iter().chain(lower.iter().rev()).copied().collect();
+
+        match pattern {
+            Pattern::Outlined => {
+                let mut closed = outline;
+                closed.push(closed[0]);
+                ctx.draw_series(std::iter::once(PathElement::new(closed, style)))?
+            }
+            _ => ctx.draw_series(std::iter::once(Polygon::new(outline, style)))?,
+        }
+        .label(name)
+        .legend(move |coord| legend_swatch(coord, style, hatching));
+
+        if let Some(hatching) = hatching {
+            // vertical lines every quarter of a day, linearly interpolated between the two curves
+            let hatching = lower
+                .iter()
+                .zip(upper.iter())
+                .tuple_windows()
+                .flat_map(|((&(x0, b0), &(_, t0)), (&(x1, b1), &(_, t1)))| {
+                    (0..4).map(move |step| {
+                        let ratio = step as f64 / 4.0;
+                        let x = x0 + (x1 - x0) * ratio;
+                        let bottom = b0 + (b1 - b0) * ratio;
+                        let top = t0 + (t1 - t0) * ratio;
+                        PathElement::new(vec![(x, bottom), (x, top)], hatching)
+                    })
+                });
+            ctx.draw_series(hatching)?;
+        }
+
+        lower = upper;
+    }
+    Ok(())
+}
+
+/// A small square used in the legend of stacked styles, hatched series being shown at half opacity
+fn legend_swatch((x, y): (i32, i32), style: ShapeStyle, hatching: Option<ShapeStyle>) -> Rectangle<(i32, i32)> {
+    let style = match hatching {
+        Some(_) => style.color.mix(0.5).filled(),
+        None => style,
+    };
+    Rectangle::new([(x - 6, y - 6), (x + 6, y + 6)], style)
+}
@@ -0,0 +1,240 @@
+use super::{
+    super::Entries,
+    GraphData, Theme, interpolation,
+    overlays::{OverlayOptions, overlays},
+};
+use crate::{
+    config::Config,
+    data::syrtime::{Granularity, SyrSpan},
+};
+use color_eyre::Result;
+use crossterm::style::{Color, Stylize};
+use itertools::Itertools;
+
+/// Width of the y-axis labels, in characters
+const Y_LABEL_WIDTH: usize = 6;
+
+/// A grid of braille characters, each character holds 2x4 dots
+struct Canvas {
+    width: usize,
+    height: usize,
+    dots: Vec<u8>,
+    colors: Vec<Option<Color>>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            dots: vec![0; width * height],
+            colors: vec![None; width * height],
+        }
+    }
+
+    /// Sets the dot at the relative coordinates, (0, 0) being the bottom left corner and (1, 1) the top right one
+    fn set(&mut self, x: f64, y: f64, color: Color) {
+        self.mark(x, y, color, &[(0, 0)]);
+    }
+
+    /// Sets the dots of the shape around the relative coordinates, the offsets being in dots with y going down
+    fn mark(&mut self, x: f64, y: f64, color: Color, shape: &[(isize, isize)]) {
+        if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+            return;
+        }
+        let dot_x = ((x * (self.width * 2 - 1) as f64).round() as usize).min(self.width * 2 - 1);
+        let dot_y = (((1.0 - y) * (self.height * 4 - 1) as f64).round() as usize).min(self.height * 4 - 1);
+        for (offset_x, offset_y) in shape {
+            if let (Some(dot_x), Some(dot_y)) = (dot_x.checked_add_signed(*offset_x), dot_y.checked_add_signed(*offset_y))
+                && dot_x < self.width * 2
+                && dot_y < self.height * 4
+            {
+                self.set_dot(dot_x, dot_y, color);
+            }
+        }
+    }
+
+    fn set_dot(&mut self, dot_x: usize, dot_y: usize, color: Color) {
+        let idx = (dot_y / 4) * self.width + dot_x / 2;
+        self.dots[idx] |= match (dot_x % 2, dot_y % 4) {
+            (0, 0) => 0x01,
+            (0, 1) => 0x02,
+            (0, 2) => 0x04,
+            (0, _) => 0x40,
+            (_, 0) => 0x08,
+            (_, 1) => 0x10,
+            (_, 2) => 0x20,
+            (_, _) => 0x80,
+        };
+        self.colors[idx] = Some(color);
+    }
+
+    fn rows(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.height).map(|row| {
+            (0..self.width)
+                .map(|col| {
+                    let idx = row * self.width + col;
+                    let chr = char::from_u32(0x2800 + self.dots[idx] as u32).unwrap_or(' ');
+                    match self.colors[idx] {
+                        Some(color) if self.dots[idx] != 0 => chr.with(color).to_string(),
+                        _ => chr.to_string(),
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+/// The dots and legend symbol of the markers of entries, entries sharing a color being told apart by their pass like in the png graph
+fn marker(pass: usize) -> (&'static [(isize, isize)], &'static str) {
+    match pass % 3 {
+        0 => (&[(0, 0)], "●"),
+        1 => (&[(0, -1), (-1, 0), (0, 0), (1, 0)], "▲"),
+        _ => (&[(-1, -1), (1, -1), (0, 0), (-1, 1), (1, 1)], "✕"),
+    }
+}
+
+fn rgb_translate(rgb: (u8, u8, u8)) -> Color {
+    Color::Rgb {
+        r: rgb.0,
+        g: rgb.1,
+        b: rgb.2,
+    }
+}
+
+/// Prints the graph to the terminal using braille characters, sized to the width of the terminal, theme being the name of the theme
+pub fn graph_terminal(
+    entries: Entries,
+    date_span: SyrSpan,
+    granularity: Granularity,
+    overlay_options: &OverlayOptions,
+    theme: Option<&str>,
+) -> Result<()> {
+    let theme = Theme::named(theme)?;
+    let fg_color = rgb_translate(theme.sum_line_rgb);
+
+    let Some(data) = GraphData::new(&entries, date_span, granularity)? else {
+        return Ok(());
+    };
+
+    let (columns, rows) = match crossterm::terminal::size() {
+        Ok((0, _)) | Ok((_, 0)) | Err(_) => (80, 24),
+        Ok((columns, rows)) => (columns as usize, rows as usize),
+    };
+    let width = columns.saturating_sub(Y_LABEL_WIDTH + 2).max(10);
+    let height = rows.saturating_sub(6).clamp(5, 20);
+    let mut canvas = Canvas::new(width, height);
+
+    // the same padding as the png graph, 1 on the left and 2 on the right
+    let max_x = (data.labels.len() + 2) as f64;
+    let scale = |(x, y): (f64, f64)| (x / max_x, y / data.max_y);
+
+    for point in data.interpolated_sum() {
+        let (x, y) = scale(point);
+        canvas.set(x, y, fg_color);
+    }
+
+    let active = data
+        .superpoints
+        .iter()
+        .filter(|(_, points)| points.iter().any(|(_, y)| *y != 0.0))
+        .collect_vec();
+    let markers = theme.markers(active.iter().map(|(name, _)| name.as_str()));
+    let marker_colors = markers.iter().map(|(rgb, _)| rgb_translate(*rgb)).collect_vec();
+    for (((_, points), color), (_, pass)) in active.iter().zip(marker_colors.iter()).zip(markers.iter()) {
+        let (shape, _) = marker(*pass);
+        for point in points.iter().filter(|(_, y)| *y != 0.0) {
+            let (x, y) = scale(*point);
+            canvas.mark(x, y, *color, shape);
+        }
+    }
+
+    // overlays are drawn last so that they stay visible, lines being sampled densely
+    let overlays = overlays(&data, overlay_options);
+    let (first, last) = data.x_range();
+    let overlay_colors = overlays
+        .iter()
+        .map(|overlay| match overlay.name.as_deref() {
+            Some(name) => active
+                .iter()
+                .zip(marker_colors.iter())
+                .find(|((other, _), _)| other == name)
+                .map(|(_, color)| *color)
+                .unwrap_or(fg_color),
+            None => fg_color,
+        })
+        .collect_vec();
+    for (overlay, color) in overlays.iter().zip(overlay_colors.iter().copied()) {
+        let lines = overlay
+            .moving_average
+            .iter()
+            .cloned()
+            .chain(overlay.trend.and_then(|trend| trend.segment(first, last)).map(Vec::from));
+        for line in lines {
+            for point in interpolation::linear(line, Config::get().graph_nb_interpolated_points) {
+                let (x, y) = scale(point);
+                canvas.set(x, y, color);
+            }
+        }
+    }
+
+    for (row, line) in canvas.rows().enumerate() {
+        let label = match row {
+            0 => format!("{:.1}", data.max_y),
+            _ if row == height / 2 => format!("{:.1}", data.max_y / 2.0),
+            _ if row == height - 1 => "0.0".to_string(),
+            _ => String::new(),
+        };
+        println!("{:>w$} {}{}", label, "┤".dim(), line, w = Y_LABEL_WIDTH);
+    }
+    println!("{:>w$} └{}", "", "─".repeat(width), w = Y_LABEL_WIDTH);
+
+    // x labels for the first, middle and last points, positioned under their column
+    let mut x_labels = vec![' '; width];
+    let middle = data.labels.len() / 2;
+    for idx in [0, middle, data.labels.len() - 1] {
+        let label = &data.labels[idx];
+        let column = (((idx + 1) as f64 / max_x) * (width - 1) as f64).round() as usize;
+        let start = column.saturating_sub(label.len() / 2).min(width.saturating_sub(label.len()));
+        for (offset, chr) in label.chars().enumerate() {
+            if let Some(slot) = x_labels.get_mut(start + offset) {
+                *slot = chr;
+            }
+        }
+    }
+    println!(
+        "{:>w$}  {}",
+        "",
+        x_labels.into_iter().collect::<String>().trim_end(),
+        w = Y_LABEL_WIDTH
+    );
+    println!();
+
+    let legend = std::iter::once(format!("{} {}", "━".with(fg_color), "Total"))
+        .chain(
+            active
+                .iter()
+                .zip(marker_colors.iter())
+                .zip(markers.iter())
+                .map(|(((name, _), color), (_, pass))| format!("{} {}", marker(*pass).1.with(*color), name)),
+        )
+        .join("  ");
+    println!("{}", legend);
+
+    for (overlay, color) in overlays.iter().zip(overlay_colors) {
+        if overlay.moving_average.is_some() {
+            println!("{} {}, moving average", "━".with(color), overlay.title());
+        }
+        if let Some(trend) = overlay.trend {
+            println!(
+                "{} {}, trend {:+.2}h/{}",
+                "┄".with(color),
+                overlay.title(),
+                trend.slope,
+                data.granularity.name()
+            );
+        }
+    }
+
+    Ok(())
+}
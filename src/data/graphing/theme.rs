@@ -0,0 +1,173 @@
+use crate::config::Config;
+use color_eyre::{Result, eyre::bail};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// The names of the themes always available, user-defined themes with the same name take precedence
+pub const BUILT_IN_THEMES: [&str; 4] = ["dark", "light", "high-contrast", "colorblind-safe"];
+
+/// The colors of a graph, missing colors of user-defined themes are taken from the dark theme
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub background_rgb: (u8, u8, u8),
+    pub foreground_rgb: (u8, u8, u8),
+    pub coarse_grid_rgb: (u8, u8, u8),
+    pub fine_grid_rgb: (u8, u8, u8),
+    pub sum_line_rgb: (u8, u8, u8),
+    /// The color of the busiest days in heatmaps, quieter days blending into the fine grid color
+    pub heatmap_rgb: (u8, u8, u8),
+    /// The colors entries are drawn with, each entry always being given the same one
+    pub marker_rgb: Vec<(u8, u8, u8)>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            background_rgb: (30, 30, 46),
+            foreground_rgb: (205, 214, 244),
+            coarse_grid_rgb: (84, 87, 108),
+            fine_grid_rgb: (49, 50, 68),
+            sum_line_rgb: (205, 214, 244),
+            heatmap_rgb: (166, 227, 161),
+            marker_rgb: vec![
+                // amaranth pink
+                (243, 167, 186),
+                // cocktail red
+                (253, 109, 114),
+                // deep saffron
+                (255, 150, 58),
+                // corn
+                (250, 234, 93),
+                // mountain lake green
+                (117, 185, 150),
+                // cerulean
+                (0, 143, 190),
+            ],
+        }
+    }
+}
+
+impl Theme {
+    fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::default()),
+            "light" => Some(Self {
+                background_rgb: (250, 250, 250),
+                foreground_rgb: (40, 40, 40),
+                coarse_grid_rgb: (190, 190, 190),
+                fine_grid_rgb: (232, 232, 232),
+                sum_line_rgb: (40, 40, 40),
+                heatmap_rgb: (33, 110, 57),
+                marker_rgb: vec![
+                    (214, 39, 40),
+                    (31, 119, 180),
+                    (255, 127, 14),
+                    (44, 160, 44),
+                    (148, 103, 189),
+                    (140, 86, 75),
+                ],
+            }),
+            "high-contrast" => Some(Self {
+                background_rgb: (0, 0, 0),
+                foreground_rgb: (255, 255, 255),
+                coarse_grid_rgb: (150, 150, 150),
+                fine_grid_rgb: (60, 60, 60),
+                sum_line_rgb: (255, 255, 255),
+                heatmap_rgb: (255, 255, 0),
+                marker_rgb: vec![
+                    (255, 255, 0),
+                    (0, 255, 255),
+                    (255, 0, 255),
+                    (0, 255, 0),
+                    (255, 128, 0),
+                    (255, 64, 64),
+                ],
+            }),
+            // Okabe-Ito, without the yellow which is hard to see on white
+            "colorblind-safe" => Some(Self {
+                background_rgb: (255, 255, 255),
+                foreground_rgb: (0, 0, 0),
+                coarse_grid_rgb: (180, 180, 180),
+                fine_grid_rgb: (230, 230, 230),
+                sum_line_rgb: (0, 0, 0),
+                heatmap_rgb: (0, 114, 178),
+                marker_rgb: vec![
+                    (230, 159, 0),
+                    (86, 180, 233),
+                    (0, 158, 115),
+                    (0, 114, 178),
+                    (213, 94, 0),
+                    (204, 121, 167),
+                ],
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the user-defined or built-in theme with this name, None standing for the configured theme
+    pub fn named(name: Option<&str>) -> Result<Self> {
+        let config = Config::get();
+        let name = name.unwrap_or(&config.graph_theme);
+        let Some(theme) = config.graph_themes.get(name).cloned().or_else(|| Self::built_in(name)) else {
+            bail!(
+                "Unknown theme '{}', available themes are: {}",
+                name,
+                BUILT_IN_THEMES
+                    .into_iter()
+                    .chain(config.graph_themes.keys().map(String::as_str))
+                    .unique()
+                    .join(", ")
+            );
+        };
+        if theme.marker_rgb.is_empty() {
+            bail!("At least one color required in the marker_rgb of theme '{}'", name);
+        }
+        Ok(theme)
+    }
+
+    /// Returns the index of the marker color of an entry, derived from its name alone
+    fn marker_idx(&self, name: &str) -> usize {
+        // FNV-1a, unlike the standard hasher its output is guaranteed not to change between releases
+        let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        (hash % self.marker_rgb.len() as u64) as usize
+    }
+
+    /// Returns the color of each entry along with the number of entries before it that were given the same color
+    pub fn markers<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Vec<((u8, u8, u8), usize)> {
+        let mut uses = vec![0; self.marker_rgb.len()];
+        names
+            .into_iter()
+            .map(|name| {
+                let idx = self.marker_idx(name);
+                uses[idx] += 1;
+                (self.marker_rgb[idx], uses[idx] - 1)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_do_not_depend_on_other_entries() {
+        let theme = Theme::default();
+        let alone = theme.markers(["THESIS"]);
+        let among_others = theme.markers(["GYM", "LEETCODE", "READING", "THESIS"]);
+        assert_eq!(alone[0].0, among_others[3].0);
+    }
+
+    #[test]
+    fn shared_colors_are_told_apart_by_pass() {
+        let theme = Theme {
+            marker_rgb: vec![(0, 0, 0)],
+            ..Theme::default()
+        };
+        let passes = theme.markers(["A", "B", "C"]).into_iter().map(|(_, pass)| pass).collect_vec();
+        assert_eq!(passes, [0, 1, 2]);
+    }
+}
@@ -0,0 +1,333 @@
+use super::{
+    Entries, Entry,
+    billing::Rounding,
+    sessions::Sessions,
+    syrtime::{Bucket, Granularity, SyrDate, SyrSpan},
+};
+use crate::output::csv_escape;
+use clap::{ValueEnum, builder::PossibleValue};
+use color_eyre::{Result, eyre::bail};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A client billed at an hourly rate for the time tracked on some entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Client {
+    pub name: String,
+    /// The names or aliases of the entries billed to the client
+    pub entries: Vec<String>,
+    pub rate: f64,
+    pub currency: String,
+    /// Hourly rates of some of the entries by name or alias, overriding the rate of the client
+    #[serde(default)]
+    pub entry_rates: BTreeMap<String, f64>,
+}
+
+impl Client {
+    /// Returns the configured client with this name, ignoring case
+    pub fn named<'a>(clients: &'a [Client], name: &str) -> Result<&'a Self> {
+        match clients.iter().find(|client| client.name.eq_ignore_ascii_case(name)) {
+            Some(client) => Ok(client),
+            None if clients.is_empty() => bail!("Unknown client '{}', no client is configured", name),
+            None => bail!(
+                "Unknown client '{}', configured clients are: {}",
+                name,
+                clients.iter().map(|client| client.name.as_str()).join(", ")
+            ),
+        }
+    }
+
+    fn matches(query: &str, entry: &Entry) -> bool {
+        let query = query.to_uppercase();
+        entry.name == query || entry.aliases.contains(&query)
+    }
+
+    /// Returns the entries billed to the client, warning about names matching none
+    pub fn entries_of<'a>(&self, entries: &'a Entries) -> Vec<&'a Entry> {
+        self.entries
+            .iter()
+            .filter_map(|query| {
+                let entry = entries.iter().find(|entry| Self::matches(query, entry));
+                if entry.is_none() {
+                    eprintln!(
+                        "Warning: No entry is named or aliased '{}', skipping it on the invoice of {}",
+                        query, self.name
+                    );
+                }
+                entry
+            })
+            .unique_by(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    pub fn rate_of(&self, entry: &Entry) -> f64 {
+        self.entry_rates
+            .iter()
+            .find(|(query, _)| Self::matches(query, entry))
+            .map(|(_, rate)| *rate)
+            .unwrap_or(self.rate)
+    }
+}
+
+/// Determines how invoices are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvoiceFormat {
+    #[default]
+    Markdown,
+    Csv,
+    Html,
+}
+
+impl ValueEnum for InvoiceFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Markdown, Self::Csv, Self::Html]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Markdown => PossibleValue::new("markdown").alias("md"),
+            Self::Csv => PossibleValue::new("csv"),
+            Self::Html => PossibleValue::new("html"),
+        })
+    }
+}
+
+/// The billable time of an entry over a day or over the whole span
+pub struct LineItem {
+    /// None when aggregated over the whole span
+    pub date: Option<SyrDate>,
+    pub entry: String,
+    pub hours: f64,
+    pub rate: f64,
+}
+
+impl LineItem {
+    /// Rounded to the cent, so that the total matches the sum of the amounts printed
+    pub fn amount(&self) -> f64 {
+        (self.hours * self.rate * 100.0).round() / 100.0
+    }
+}
+
+pub struct Invoice<'a> {
+    pub client: &'a Client,
+    pub span: SyrSpan,
+    pub lines: Vec<LineItem>,
+}
+
+impl<'a> Invoice<'a> {
+    /// Bills the time tracked within the span on the entries of the client, rounded per entry per day or per session, with one line per entry per day unless aggregated
+    pub fn new(client: &'a Client, entries: &Entries, span: SyrSpan, rounding: Rounding, per_day: bool) -> Result<Self> {
+        let buckets = match per_day {
+            true => Granularity::Day.buckets(span),
+            false => vec![Bucket {
+                start: span.start.into(),
+                end: span.end.into(),
+                label: String::new(),
+            }],
+        };
+        let billed = client.entries_of(entries);
+        let sessions: Vec<Sessions> = billed.iter().map(|entry| rounding.sessions(entry)).collect::<Result<_>>()?;
+        let lines = buckets
+            .iter()
+            .flat_map(|bucket| {
+                billed.iter().zip(sessions.iter()).filter_map(move |(entry, sessions)| {
+                    bucket.duration_opt(&entry.blocs)?;
+                    Some(LineItem {
+                        date: per_day.then_some(bucket.start),
+                        entry: entry.name.clone(),
+                        hours: rounding.billable(bucket, &entry.blocs, sessions) / 3600.0,
+                        rate: client.rate_of(entry),
+                    })
+                })
+            })
+            .collect();
+        Ok(Self { client, span, lines })
+    }
+
+    pub fn total_hours(&self) -> f64 {
+        self.lines.iter().fold(0.0, |total, line| total + line.hours)
+    }
+
+    pub fn total_amount(&self) -> f64 {
+        self.lines.iter().fold(0.0, |total, line| total + line.amount())
+    }
+
+    fn has_dates(&self) -> bool {
+        self.lines.iter().any(|line| line.date.is_some())
+    }
+
+    fn money(&self, amount: f64) -> String {
+        format!("{:.2} {}", amount, self.client.currency)
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "Invoice - {} - {} to {}",
+            self.client.name,
+            SyrDate::from(self.span.start),
+            SyrDate::from(self.span.end)
+        )
+    }
+
+    pub fn render(&self, format: InvoiceFormat) -> String {
+        match format {
+            InvoiceFormat::Markdown => self.to_markdown(),
+            InvoiceFormat::Csv => self.to_csv(),
+            InvoiceFormat::Html => self.to_html(),
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        let dates = self.has_dates();
+        let mut lines = vec![format!("# {}", markdown_escape(&self.title())), String::new()];
+        lines.push(format!("|{} Entry | Hours | Rate | Amount |", if dates { " Date |" } else { "" }));
+        lines.push(format!("|{}---|--:|--:|--:|", if dates { "---|" } else { "" }));
+        for line in self.lines.iter() {
+            lines.push(format!(
+                "|{} {} | {:.2} | {} | {} |",
+                line.date.as_ref().map(|date| format!(" {} |", date)).unwrap_or_default(),
+                markdown_escape(&line.entry),
+                line.hours,
+                markdown_escape(&self.money(line.rate)),
+                markdown_escape(&self.money(line.amount()))
+            ));
+        }
+        lines.push(format!(
+            "|{} **Total** | **{:.2}** | | **{}** |",
+            if dates { " |" } else { "" },
+            self.total_hours(),
+            markdown_escape(&self.money(self.total_amount()))
+        ));
+        lines.join("\n") + "\n"
+    }
+
+    fn to_csv(&self) -> String {
+        let mut lines = vec!["date,entry,hours,rate,amount,currency".to_string()];
+        let currency = csv_escape(&self.client.currency);
+        for line in self.lines.iter() {
+            lines.push(format!(
+                "{},{},{:.2},{:.2},{:.2},{}",
+                line.date.map(|date| date.as_string_iso()).unwrap_or_default(),
+                csv_escape(&line.entry),
+                line.hours,
+                line.rate,
+                line.amount(),
+                currency
+            ));
+        }
+        lines.push(format!(",Total,{:.2},,{:.2},{}", self.total_hours(), self.total_amount(), currency));
+        lines.join("\n") + "\n"
+    }
+
+    fn to_html(&self) -> String {
+        let dates = self.has_dates();
+        let cell = |tag: &str, text: &str| format!("<{tag}>{}</{tag}>", html_escape(text));
+        let row = |cells: Vec<String>| format!("      <tr>{}</tr>", cells.concat());
+        let mut rows = vec![row(dates
+            .then_some("Date")
+            .into_iter()
+            .chain(["Entry", "Hours", "Rate", "Amount"])
+            .map(|header| cell("th", header))
+            .collect())];
+        for line in self.lines.iter() {
+            rows.push(row(line
+                .date
+                .iter()
+                .map(|date| cell("td", &date.to_string()))
+                .chain([
+                    cell("td", &line.entry),
+                    cell("td", &format!("{:.2}", line.hours)),
+                    cell("td", &self.money(line.rate)),
+                    cell("td", &self.money(line.amount())),
+                ])
+                .collect()));
+        }
+        rows.push(row(dates
+            .then(|| cell("th", ""))
+            .into_iter()
+            .chain([
+                cell("th", "Total"),
+                cell("th", &format!("{:.2}", self.total_hours())),
+                cell("th", ""),
+                cell("th", &self.money(self.total_amount())),
+            ])
+            .collect()));
+        let title = html_escape(&self.title());
+        format!(
+            "<!DOCTYPE html>\n<html>\n  <head>\n    <meta charset=\"utf-8\">\n    <title>{title}</title>\n  </head>\n  <body>\n    <h1>{title}</h1>\n    <table>\n{}\n    </table>\n  </body>\n</html>\n",
+            rows.join("\n")
+        )
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escapes the characters that would break a Markdown table cell
+fn markdown_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        billing::{RoundingMode, RoundingUnit},
+        syrtime::Blocs,
+    };
+
+    #[test]
+    fn bills_rounded_time_at_entry_rates() {
+        let date = jiff::civil::date(2026, 10, 5);
+        let entry = |name: &str, alias: &str, minutes: f64| {
+            let blocs = Blocs([(SyrDate::from(date), minutes * 60.0)].into_iter().collect());
+            Entry::new(name.to_string(), vec![alias.to_string()], blocs, true)
+        };
+        let entries = Entries::from(vec![entry("THESIS", "PAPER", 50.0), entry("READING", "BOOKS", 20.0)]);
+        let client = Client {
+            name: "ACME".to_string(),
+            entries: vec!["paper".to_string(), "books".to_string()],
+            rate: 80.0,
+            currency: "EUR".to_string(),
+            entry_rates: [("BOOKS".to_string(), 60.0)].into_iter().collect(),
+        };
+        let rounding = Rounding {
+            increment_minutes: 30,
+            mode: RoundingMode::Up,
+            per: RoundingUnit::Day,
+        };
+        let invoice = Invoice::new(&client, &entries, SyrSpan::from_start_and_end(date, date), rounding, false).unwrap();
+        assert_eq!(invoice.lines.iter().map(|line| line.hours).collect_vec(), [1.0, 0.5]);
+        assert_eq!(invoice.total_amount(), 80.0 + 30.0);
+    }
+
+    #[test]
+    fn markdown_cells_escape_pipes() {
+        let date = jiff::civil::date(2026, 10, 5);
+        let blocs = Blocs([(SyrDate::from(date), 3600.0)].into_iter().collect());
+        let entries = Entries::from(vec![Entry::new("A|B".to_string(), vec![], blocs, true)]);
+        let client = Client {
+            name: "X|Y".to_string(),
+            entries: vec!["a|b".to_string()],
+            rate: 10.0,
+            currency: "EUR".to_string(),
+            entry_rates: Default::default(),
+        };
+        let invoice = Invoice::new(
+            &client,
+            &entries,
+            SyrSpan::from_start_and_end(date, date),
+            Rounding::default(),
+            false,
+        )
+        .unwrap();
+        let markdown = invoice.render(InvoiceFormat::Markdown);
+        assert!(markdown.starts_with("# Invoice - X\\|Y - "));
+        assert!(markdown.contains("| A\\|B | 1.00 |"));
+    }
+}
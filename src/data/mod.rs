@@ -1,13 +1,32 @@
+pub mod billing;
+pub mod checkin;
 pub mod entries;
 pub mod entry;
+pub mod goal;
 pub mod graphing;
+pub mod invoice;
+pub mod sessions;
+pub mod stats;
 pub mod syrtime;
 
+pub use checkin::CheckIn;
 pub use entries::Entries;
 pub use entry::Entry;
+pub use goal::Goal;
 
 pub enum IndexOptions {
     All,
     Indexed,
     Unindexed,
 }
+
+/// Determines how an entry is picked out of the candidates matching a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Prompts the user to confirm or pick among the best matches
+    Interactive,
+    /// Only accepts an entry whose name or alias is exactly the query
+    Exact,
+    /// Accepts the top-scoring match without prompting
+    Best,
+}
@@ -0,0 +1,51 @@
+use super::syrtime::SyrDate;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::Read, path::PathBuf};
+
+/// The durations of the timed sessions of an entry per day, stored as a '.ses' file next to the entry file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Sessions(pub BTreeMap<SyrDate, Vec<f64>>);
+
+impl std::ops::Deref for Sessions {
+    type Target = BTreeMap<SyrDate, Vec<f64>>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Sessions {
+    fn filepath(name: &str) -> PathBuf {
+        crate::dirs::Dirs::get().data_dir().join([name, ".ses"].concat())
+    }
+
+    /// Returns the sessions recorded for the entry, none if its file does not exist
+    pub fn load(name: &str) -> Result<Self> {
+        let mut buffer: Vec<u8> = Vec::new();
+        match std::fs::OpenOptions::new().read(true).open(Self::filepath(name)) {
+            Ok(mut file) => file.read_to_end(&mut buffer)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(ijson::from_value(&serde_json::from_slice(&buffer)?)?)
+    }
+
+    /// Appends a session to the ones recorded for the entry on the date
+    pub fn record(name: &str, date: &SyrDate, duration: f64) -> Result<()> {
+        if duration <= 0.0 {
+            return Ok(());
+        }
+        let mut sessions = Self::load(name)?;
+        sessions.0.entry(*date).or_default().push(duration);
+        std::fs::write(Self::filepath(name), serde_json::to_vec(&ijson::to_value(&sessions)?)?)?;
+        Ok(())
+    }
+
+    /// Removes the sessions recorded for the entry, if any
+    pub fn delete(name: &str) -> Result<()> {
+        match std::fs::remove_file(Self::filepath(name)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
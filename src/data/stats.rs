@@ -0,0 +1,206 @@
+use super::syrtime::{Granularity, SyrDate, SyrSpan, WeekdayFormatting};
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+
+/// Consistency statistics computed from the daily totals of a span, a day being active when anything was tracked
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    /// The number of consecutive active days up to the end of the span, an inactive last day not breaking it as it may not be over
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    /// The first and last days of the longest streak
+    #[serde(serialize_with = "serialize_iso")]
+    pub longest_streak_dates: Option<(SyrDate, SyrDate)>,
+    pub active_days: usize,
+    pub days: usize,
+    /// The total number of seconds tracked
+    pub total: f64,
+    /// The mean number of seconds tracked per active day
+    pub mean: f64,
+    /// The median number of seconds tracked per active day
+    pub median: f64,
+    /// The weekday with the most seconds tracked on average, along with that average
+    pub busiest_weekday: Option<(String, f64)>,
+    /// The label of the week with the most seconds tracked, along with its total
+    pub best_week: Option<(String, f64)>,
+}
+
+impl Stats {
+    /// Computes the statistics of the span, seconds returning the time tracked on a given day
+    pub fn new(span: SyrSpan, seconds: impl Fn(&SyrDate) -> f64) -> Self {
+        let daily = span.into_iter().map(|date| (date, seconds(&date))).collect_vec();
+        let is_active = |(_, seconds): &&(SyrDate, f64)| *seconds > 0.0;
+
+        let (mut longest_streak, mut longest_streak_dates, mut streak) = (0, None, 0);
+        for (idx, (date, seconds)) in daily.iter().enumerate() {
+            if *seconds > 0.0 {
+                streak += 1;
+                if streak > longest_streak {
+                    longest_streak = streak;
+                    longest_streak_dates = Some((daily[idx + 1 - streak].0, *date));
+                }
+            } else {
+                streak = 0;
+            }
+        }
+        let ongoing = match daily.last() {
+            Some((_, seconds)) if *seconds <= 0.0 => &daily[..daily.len() - 1],
+            _ => &daily[..],
+        };
+        let current_streak = ongoing.iter().rev().take_while(is_active).count();
+
+        let mut active = daily.iter().filter(is_active).map(|(_, seconds)| *seconds).collect_vec();
+        active.sort_by(f64::total_cmp);
+        let total = active.iter().fold(0.0, |total, seconds| total + seconds);
+        let mean = if active.is_empty() { 0.0 } else { total / active.len() as f64 };
+        let median = match active.len() {
+            0 => 0.0,
+            len if len.is_multiple_of(2) => (active[len / 2 - 1] + active[len / 2]) / 2.0,
+            len => active[len / 2],
+        };
+
+        let busiest_weekday = daily
+            .iter()
+            .into_group_map_by(|(date, _)| date.weekday())
+            .into_iter()
+            .map(|(weekday, days)| {
+                let seconds = days.iter().fold(0.0, |total, (_, seconds)| total + seconds);
+                (weekday, seconds / days.len() as f64)
+            })
+            .filter(|(_, seconds)| *seconds > 0.0)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(weekday, seconds)| (WeekdayFormatting::to_string(&weekday), seconds));
+
+        let best_week = Granularity::Week
+            .buckets(span)
+            .into_iter()
+            .map(|bucket| {
+                let seconds = daily
+                    .iter()
+                    .filter(|(date, _)| (bucket.start..=bucket.end).contains(date))
+                    .fold(0.0, |total, (_, seconds)| total + seconds);
+                (bucket.label, seconds)
+            })
+            .filter(|(_, seconds)| *seconds > 0.0)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        Self {
+            current_streak,
+            longest_streak,
+            longest_streak_dates,
+            active_days: active.len(),
+            days: daily.len(),
+            total,
+            mean,
+            median,
+            busiest_weekday,
+            best_week,
+        }
+    }
+
+    /// The names of the flat fields of the csv and plain outputs
+    pub const FIELDS: [&str; 13] = [
+        "current_streak",
+        "longest_streak",
+        "longest_streak_start",
+        "longest_streak_end",
+        "active_days",
+        "days",
+        "total",
+        "mean",
+        "median",
+        "busiest_weekday",
+        "busiest_weekday_seconds",
+        "best_week",
+        "best_week_seconds",
+    ];
+
+    /// Returns the statistics as flat fields in the order of FIELDS, absent ones being empty
+    pub fn fields(&self) -> [String; 13] {
+        let (longest_streak_start, longest_streak_end) = self
+            .longest_streak_dates
+            .map(|(start, end)| (start.as_string_iso(), end.as_string_iso()))
+            .unwrap_or_default();
+        let (busiest_weekday, busiest_weekday_seconds) = self
+            .busiest_weekday
+            .clone()
+            .map(|(weekday, seconds)| (weekday, seconds.to_string()))
+            .unwrap_or_default();
+        let (best_week, best_week_seconds) = self
+            .best_week
+            .clone()
+            .map(|(label, seconds)| (label, seconds.to_string()))
+            .unwrap_or_default();
+        [
+            self.current_streak.to_string(),
+            self.longest_streak.to_string(),
+            longest_streak_start,
+            longest_streak_end,
+            self.active_days.to_string(),
+            self.days.to_string(),
+            self.total.to_string(),
+            self.mean.to_string(),
+            self.median.to_string(),
+            busiest_weekday,
+            busiest_weekday_seconds,
+            best_week,
+            best_week_seconds,
+        ]
+    }
+}
+
+/// Machine-readable output uses ISO 8601 dates
+fn serialize_iso<S: Serializer>(dates: &Option<(SyrDate, SyrDate)>, serializer: S) -> Result<S::Ok, S::Error> {
+    dates
+        .map(|(start, end)| (start.as_string_iso(), end.as_string_iso()))
+        .serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::ToSpan;
+
+    /// Builds the statistics of consecutive days starting on Monday the 5th of October 2026
+    fn stats(hours: &[f64]) -> Stats {
+        let start = jiff::civil::date(2026, 10, 5);
+        let end = start.saturating_add((hours.len() as i64 - 1).days());
+        Stats::new(SyrSpan::from_start_and_end(start, end), |date| {
+            hours[(**date - start).get_days() as usize] * 3600.0
+        })
+    }
+
+    #[test]
+    fn streaks() {
+        let stats = stats(&[1.0, 2.0, 0.0, 1.0, 1.0, 1.0, 0.0, 3.0, 1.0]);
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.active_days, 7);
+        assert_eq!(stats.longest_streak_dates.map(|(start, _)| start.day()), Some(8));
+    }
+
+    #[test]
+    fn an_inactive_last_day_does_not_break_the_current_streak() {
+        assert_eq!(stats(&[1.0, 1.0, 0.0]).current_streak, 2);
+        assert_eq!(stats(&[1.0, 0.0, 0.0]).current_streak, 0);
+    }
+
+    #[test]
+    fn averages_only_count_active_days() {
+        let stats = stats(&[1.0, 0.0, 3.0, 0.0, 4.0, 0.0, 0.0]);
+        assert_eq!(stats.mean, 8.0 / 3.0 * 3600.0);
+        assert_eq!(stats.median, 3.0 * 3600.0);
+        assert_eq!(stats.busiest_weekday.map(|(weekday, _)| weekday).as_deref(), Some("Friday"));
+    }
+
+    #[test]
+    fn flat_fields_leave_absent_statistics_empty() {
+        let fields = stats(&[0.0, 0.0]).fields();
+        assert_eq!(fields.len(), Stats::FIELDS.len());
+        assert_eq!(
+            fields[Stats::FIELDS.iter().position(|field| *field == "longest_streak_start").unwrap()],
+            ""
+        );
+        assert_eq!(stats(&[1.0]).fields()[2], "2026-10-05");
+    }
+}
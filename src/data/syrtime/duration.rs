@@ -0,0 +1,90 @@
+use color_eyre::{
+    Result,
+    eyre::{Context, OptionExt, bail},
+};
+
+/// Parses a duration into seconds, such as '1h30m', '90m', '1:30', '1:30:15', '1.5h', '45s' or '-15m'
+pub fn parse_duration(s: &str) -> Result<f64> {
+    let input = s.trim().to_lowercase();
+    let (sign, input) = match input.strip_prefix('-') {
+        Some(input) => (-1.0, input),
+        None => (1.0, input.strip_prefix('+').unwrap_or(&input)),
+    };
+    if input.is_empty() {
+        bail!("Failed to parse duration, empty duration");
+    }
+
+    let seconds = if input.contains(':') {
+        let parts = input
+            .split(':')
+            .map(str::parse::<u32>)
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Failed to parse duration '{}', expected h:mm or h:mm:ss", s))?;
+        let (hours, minutes, seconds) = match parts.as_slice() {
+            [hours, minutes] if *minutes < 60 => (*hours, *minutes, 0),
+            [hours, minutes, seconds] if *minutes < 60 && *seconds < 60 => (*hours, *minutes, *seconds),
+            _ => bail!("Failed to parse duration '{}', expected h:mm or h:mm:ss", s),
+        };
+        hours
+            .checked_mul(3600)
+            .and_then(|hours| hours.checked_add(minutes * 60 + seconds))
+            .ok_or_eyre(format!("Failed to parse duration '{}', too long", s))? as f64
+    } else {
+        let mut seconds = 0.0;
+        let mut rest = input;
+        while !rest.is_empty() {
+            let unit_start = rest
+                .find(|char: char| !(char.is_ascii_digit() || char == '.'))
+                .ok_or_eyre(format!("Failed to parse duration '{}', missing unit, expected 'h', 'm' or 's'", s))?;
+            let (number, tail) = rest.split_at(unit_start);
+            let unit_end = tail.find(|char: char| char.is_ascii_digit() || char == '.').unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(unit_end);
+            let number = number
+                .parse::<f64>()
+                .with_context(|| format!("Failed to parse duration '{}', invalid number", s))?;
+            seconds += number
+                * match unit.trim() {
+                    "h" | "hr" | "hrs" | "hour" | "hours" => 3600.0,
+                    "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+                    "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+                    unit => bail!(
+                        "Failed to parse duration '{}', unknown unit '{}', expected 'h', 'm' or 's'",
+                        s,
+                        unit
+                    ),
+                };
+            rest = tail;
+        }
+        seconds
+    };
+    Ok(sign * seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepted_durations() {
+        for (input, seconds) in [
+            ("1h30m", 5400.0),
+            ("1h 30m", 5400.0),
+            ("90m", 5400.0),
+            ("1:30", 5400.0),
+            ("1:30:15", 5415.0),
+            ("1.5h", 5400.0),
+            ("45s", 45.0),
+            ("-15m", -900.0),
+            ("2 hours", 7200.0),
+        ] {
+            assert_eq!(parse_duration(input).unwrap(), seconds, "{}", input);
+        }
+    }
+
+    #[test]
+    fn rejected_durations() {
+        for input in ["", "90", "h", "1x", "1:75", "thesis", "-", "99999999:00", "99999999999:00:00"] {
+            assert!(parse_duration(input).is_err(), "{}", input);
+        }
+    }
+}
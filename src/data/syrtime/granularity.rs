@@ -0,0 +1,124 @@
+use super::{Blocs, MONTH_NAMES, SyrDate, SyrSpan};
+use clap::{ValueEnum, builder::PossibleValue};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// The size of the buckets dates are grouped into
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Granularity {
+    #[default]
+    Day,
+    /// Weeks starting on the configured weekday, labelled with their ISO week number
+    Week,
+    /// Calendar months
+    Month,
+}
+
+impl ValueEnum for Granularity {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Day, Self::Week, Self::Month]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Day => PossibleValue::new("day").alias("daily"),
+            Self::Week => PossibleValue::new("week").alias("weekly"),
+            Self::Month => PossibleValue::new("month").alias("monthly"),
+        })
+    }
+}
+
+impl Granularity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// Splits the span into consecutive buckets, the first and last ones being cut short by the span
+    pub fn buckets(&self, span: SyrSpan) -> Vec<Bucket> {
+        span.into_iter()
+            .chunk_by(|date| self.key(date))
+            .into_iter()
+            .filter_map(|(_, mut dates)| {
+                let start = dates.next()?;
+                let end = dates.last().unwrap_or(start);
+                Some(Bucket {
+                    start,
+                    end,
+                    label: self.label(&start),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the whole day, week or month containing the date, regardless of any span
+    pub fn bucket(&self, date: SyrDate) -> Bucket {
+        let (start, end) = match self {
+            Self::Day => (*date, *date),
+            Self::Week => {
+                let week = Self::week_of(&date);
+                (week.start, week.end)
+            }
+            Self::Month => (date.first_of_month(), date.last_of_month()),
+        };
+        Bucket {
+            start: start.into(),
+            end: end.into(),
+            label: self.label(&date),
+        }
+    }
+
+    /// Dates sharing the same key belong to the same bucket
+    fn key(&self, date: &SyrDate) -> (i16, i16) {
+        match self {
+            Self::Day => (date.year(), date.day_of_year()),
+            Self::Week => {
+                let start = Self::week_of(date).start;
+                (start.year(), start.day_of_year())
+            }
+            Self::Month => (date.year(), date.month() as i16),
+        }
+    }
+
+    /// Dates too close to the limits of the calendar for their week to exist are given a week of their own
+    fn week_of(date: &SyrDate) -> SyrSpan {
+        SyrSpan::week_of(**date).unwrap_or(SyrSpan::from_start_and_end(**date, **date))
+    }
+
+    fn label(&self, date: &SyrDate) -> String {
+        match self {
+            Self::Day => date.to_string(),
+            Self::Week => {
+                let (year, week) = Self::week_of(date).iso_week();
+                format!("W{:0>2} {}", week, year)
+            }
+            Self::Month => format!("{} {}", &MONTH_NAMES[date.month() as usize - 1][..3], date.year()),
+        }
+    }
+}
+
+/// A run of consecutive dates sharing the same day, week or month
+pub struct Bucket {
+    /// The first date, inclusive
+    pub start: SyrDate,
+    /// The last date, inclusive
+    pub end: SyrDate,
+    pub label: String,
+}
+
+impl Bucket {
+    /// Returns the number of seconds tracked within the bucket, None if no bloc falls within it
+    pub fn duration_opt(&self, blocs: &Blocs) -> Option<f64> {
+        blocs
+            .range(self.start..=self.end)
+            .map(|(_, duration)| *duration)
+            .reduce(|a, b| a + b)
+    }
+
+    pub fn duration(&self, blocs: &Blocs) -> f64 {
+        self.duration_opt(blocs).unwrap_or(0.0)
+    }
+}
@@ -1,9 +1,13 @@
 pub mod blocs;
+pub mod duration;
+pub mod granularity;
 pub mod syrdate;
 pub mod syrspan;
 pub mod traits;
 
 pub use blocs::Blocs;
-pub use syrdate::SyrDate;
-pub use syrspan::SyrSpan;
-pub use traits::{TimeFormatting, WeekdayFormatting};
+pub use duration::parse_duration;
+pub use granularity::{Bucket, Granularity};
+pub use syrdate::{DateFormat, MONTH_NAMES, SyrDate, TODAY};
+pub use syrspan::{SyrSpan, week_start};
+pub use traits::{TIME_FORMAT, TimeFormat, TimeFormatting, WeekdayFormatting};
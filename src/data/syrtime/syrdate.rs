@@ -1,6 +1,46 @@
+use crate::config::CONFIG;
 use color_eyre::eyre::{Context, OptionExt, bail};
+use jiff::{ToSpan, civil::Weekday};
 use serde::{Deserialize, Serialize, de::Visitor};
-use std::str::FromStr;
+use std::{str::FromStr, sync::OnceLock};
+
+/// The date commands count as today, set once the night owl extension is applied
+pub static TODAY: OnceLock<SyrDate> = OnceLock::new();
+
+/// The names of the months, their first three letters being used as abbreviations
+pub const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Determines how dates are displayed, and which of the day and month comes first when parsing dates separated by '/'
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateFormat {
+    #[default]
+    #[serde(rename = "dd/mm/yyyy")]
+    DayMonthYear,
+    #[serde(rename = "yyyy-mm-dd")]
+    Iso,
+    #[serde(rename = "mm/dd/yyyy")]
+    MonthDayYear,
+}
+
+impl DateFormat {
+    /// Returns the configured format, dd/mm/yyyy when the configuration is not loaded
+    pub fn current() -> Self {
+        CONFIG.get().map(|config| config.date_format).unwrap_or_default()
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SyrDate(jiff::civil::Date);
@@ -12,43 +52,141 @@ impl SyrDate {
     pub fn as_string_with_formatting(&self, sep_char: char) -> String {
         format!("{:0>2}{sep_char}{:0>2}{sep_char}{:0>4}", self.day(), self.month(), self.year())
     }
-}
+    pub fn as_string_iso(&self) -> String {
+        format!("{:0>4}-{:0>2}-{:0>2}", self.year(), self.month(), self.day())
+    }
 
-impl std::fmt::Display for SyrDate {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:0>2}/{:0>2}/{:0>4}", self.day(), self.month(), self.year())
+    /// Parses absolute, relative and natural-language dates, relative ones being resolved against today
+    pub fn parse(s: &str, today: jiff::civil::Date, format: DateFormat) -> color_eyre::Result<Self> {
+        let input = s.trim().to_lowercase();
+        match Self::parse_relative(&input, today)? {
+            Some(date) => Ok(Self::from(date)),
+            None => Self::parse_numeric(&input, format).with_context(|| {
+                format!(
+                    "Failed to parse date '{}', expected dd/mm/yyyy, yyyy-mm-dd, 'today', 'yesterday', a weekday such as 'monday' or 'last friday', an offset such as '-3d' or '2w ago', or a month name such as 'march' or 'march 2025'",
+                    s
+                )
+            }),
+        }
     }
-}
 
-impl From<jiff::civil::Date> for SyrDate {
-    fn from(value: jiff::civil::Date) -> Self {
-        Self::new(value)
+    /// Weekdays stand for the most recent one, today included unless preceded by 'last', and month names for the first day of the most recent one
+    fn parse_relative(input: &str, today: jiff::civil::Date) -> color_eyre::Result<Option<jiff::civil::Date>> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let date = match words.as_slice() {
+            ["today"] => today,
+            ["yesterday"] => today.yesterday()?,
+            [word] if let Some(weekday) = parse_weekday(word) => match today.weekday() == weekday {
+                true => today,
+                false => today.nth_weekday(-1, weekday)?,
+            },
+            ["last", word] if let Some(weekday) = parse_weekday(word) => today.nth_weekday(-1, weekday)?,
+            [word] if let Some(month) = parse_month(word) => {
+                let year = today.year() - i16::from(month > today.month());
+                jiff::civil::Date::new(year, month, 1)?
+            }
+            [word, year] if let Some(month) = parse_month(word) => {
+                jiff::civil::Date::new(year.parse().context("Failed to parse date, invalid year")?, month, 1)?
+            }
+            _ => match parse_offset(&words) {
+                Some(offset) => today.checked_add(offset)?,
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(date))
     }
-}
 
-impl FromStr for SyrDate {
-    type Err = color_eyre::eyre::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Parses three numbers separated by '/', '.', '-' or '_', a four digit first number standing for ISO 8601
+    fn parse_numeric(input: &str, format: DateFormat) -> color_eyre::Result<Self> {
         let split_char = ['/', '.', '-', '_']
             .into_iter()
-            .filter(|char| s.contains(*char))
+            .filter(|char| input.contains(*char))
             .nth(0)
             .ok_or_eyre("Failed to parse date, no separator character detected, ('/', '.', '-', '_')")?;
-        let input: Vec<&str> = s.split(split_char).collect();
+        let input: Vec<&str> = input.split(split_char).collect();
         if input.len() != 3 {
             bail!("Failed to parse date, invalid date format, expected dd/mm/yyyy, or with '/' alternatives such as '.', '_', or '-'");
         }
+        let (year, month, day) = match format {
+            _ if input[0].len() == 4 => (input[0], input[1], input[2]),
+            DateFormat::MonthDayYear => (input[2], input[0], input[1]),
+            DateFormat::DayMonthYear | DateFormat::Iso => (input[2], input[1], input[0]),
+        };
         Ok(Self::from(
             jiff::civil::Date::new(
-                input[2].parse::<i16>().context("Failed to parse date, invalid year")?,
-                input[1].parse::<i8>().context("Failed to parse date, invalid month")?,
-                input[0].parse::<i8>().context("Failed to parse date, invalid day")?,
+                year.parse::<i16>().context("Failed to parse date, invalid year")?,
+                month.parse::<i8>().context("Failed to parse date, invalid month")?,
+                day.parse::<i8>().context("Failed to parse date, invalid day")?,
             )
             .context("Failed to parse date, invalid date format, expected dd/mm/yyyy, or with '/' alternatives such as '.', '_', or '-'")?,
         ))
     }
 }
 
+/// Accepts full names and abbreviations of at least three letters
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    Weekday::Monday
+        .cycle_forward()
+        .take(7)
+        .find(|weekday| word.len() >= 3 && super::WeekdayFormatting::to_string(weekday).to_lowercase().starts_with(word))
+}
+
+/// Accepts full names and abbreviations of at least three letters
+fn parse_month(word: &str) -> Option<i8> {
+    MONTH_NAMES
+        .iter()
+        .position(|name| word.len() >= 3 && name.get(..word.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(word)))
+        .map(|idx| idx as i8 + 1)
+}
+
+/// Parses '-3d', '+1w', '2w ago' or '3 days ago', with 'd', 'w', 'm' and 'y' standing for days, weeks, months and years
+fn parse_offset(words: &[&str]) -> Option<jiff::Span> {
+    let (sign, offset) = match words {
+        [words @ .., "ago"] if !words.is_empty() => (-1, words.concat()),
+        _ => {
+            let offset = words.concat();
+            match offset.strip_prefix('-') {
+                Some(offset) => (-1, offset.to_string()),
+                None => (1, offset.strip_prefix('+')?.to_string()),
+            }
+        }
+    };
+    let split = offset.find(|char: char| !char.is_ascii_digit())?;
+    let (number, unit) = offset.split_at(split);
+    let number = sign * number.parse::<i64>().ok()?;
+    match unit {
+        "d" | "day" | "days" => number.days().into(),
+        "w" | "week" | "weeks" => number.weeks().into(),
+        "m" | "month" | "months" => number.months().into(),
+        "y" | "year" | "years" => number.years().into(),
+        _ => None,
+    }
+}
+
+impl std::fmt::Display for SyrDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match DateFormat::current() {
+            DateFormat::DayMonthYear => write!(f, "{}", self.as_string_with_formatting('/')),
+            DateFormat::Iso => write!(f, "{}", self.as_string_iso()),
+            DateFormat::MonthDayYear => write!(f, "{:0>2}/{:0>2}/{:0>4}", self.month(), self.day(), self.year()),
+        }
+    }
+}
+
+impl From<jiff::civil::Date> for SyrDate {
+    fn from(value: jiff::civil::Date) -> Self {
+        Self::new(value)
+    }
+}
+
+impl FromStr for SyrDate {
+    type Err = color_eyre::eyre::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let today = TODAY.get().map(|today| **today).unwrap_or_else(|| jiff::Zoned::now().date());
+        Self::parse(s, today, DateFormat::current())
+    }
+}
+
 impl TryFrom<&str> for SyrDate {
     type Error = color_eyre::eyre::Error;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -68,7 +206,8 @@ impl Serialize for SyrDate {
     where
         S: serde::Serializer,
     {
-        self.to_string().serialize(serializer)
+        // stored dates never depend on the configured display format
+        self.as_string_with_formatting('/').serialize(serializer)
     }
 }
 
@@ -107,13 +246,65 @@ impl Visitor<'_> for SyrDateVisitor {
     where
         E: serde::de::Error,
     {
-        SyrDate::try_from(v).or(Err(E::custom("Failed to parse date, invalid date format")))
+        SyrDate::parse_numeric(v, DateFormat::DayMonthYear).or(Err(E::custom("Failed to parse date, invalid date format")))
     }
 
     fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        SyrDate::try_from(&v).or(Err(E::custom("Failed to parse date, invalid date format")))
+        SyrDate::parse_numeric(&v, DateFormat::DayMonthYear).or(Err(E::custom("Failed to parse date, invalid date format")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses the input as if today was Sunday the 18th of October 2026
+    fn parse(input: &str) -> String {
+        SyrDate::parse(input, jiff::civil::date(2026, 10, 18), DateFormat::DayMonthYear)
+            .unwrap()
+            .as_string_iso()
+    }
+
+    #[test]
+    fn absolute_dates() {
+        assert_eq!(parse("05/10/2026"), "2026-10-05");
+        assert_eq!(parse("2026-10-05"), "2026-10-05");
+        assert_eq!(
+            SyrDate::parse("10/05/2026", jiff::civil::date(2026, 10, 18), DateFormat::MonthDayYear)
+                .unwrap()
+                .as_string_iso(),
+            "2026-10-05"
+        );
+    }
+
+    #[test]
+    fn relative_dates() {
+        assert_eq!(parse("today"), "2026-10-18");
+        assert_eq!(parse("Yesterday"), "2026-10-17");
+        assert_eq!(parse("sunday"), "2026-10-18");
+        assert_eq!(parse("last sunday"), "2026-10-11");
+        assert_eq!(parse("mon"), "2026-10-12");
+        assert_eq!(parse("last friday"), "2026-10-16");
+        assert_eq!(parse("-3d"), "2026-10-15");
+        assert_eq!(parse("2w ago"), "2026-10-04");
+        assert_eq!(parse("1 month ago"), "2026-09-18");
+    }
+
+    #[test]
+    fn month_names() {
+        assert_eq!(parse("march"), "2026-03-01");
+        assert_eq!(parse("december"), "2025-12-01");
+        assert_eq!(parse("oct 2024"), "2024-10-01");
+    }
+
+    #[test]
+    fn invalid_dates() {
+        let today = jiff::civil::date(2026, 10, 18);
+        for input in ["3d", "someday", "31/02/2026", "last"] {
+            assert!(SyrDate::parse(input, today, DateFormat::DayMonthYear).is_err());
+        }
     }
 }
@@ -1,7 +1,17 @@
-use jiff::{ToSpan, civil::Date};
+use jiff::{
+    ToSpan,
+    civil::{Date, Weekday},
+};
 
 use super::syrdate::SyrDate;
+use crate::config::CONFIG;
 
+/// Returns the configured first day of the week, Monday when the configuration is not loaded
+pub fn week_start() -> Weekday {
+    CONFIG.get().map(|config| config.week_start).unwrap_or(Weekday::Monday)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct SyrSpan {
     /// The start date, inclusive
     pub start: Date,
@@ -19,6 +29,17 @@ impl SyrSpan {
             end,
         }
     }
+    /// The week containing the date, starting on the configured weekday
+    pub fn week_of(date: Date) -> Result<Self, jiff::Error> {
+        let start = date.checked_sub((date.weekday().since(week_start()) as i64).days())?;
+        Ok(Self::from_start_and_end(start, start.checked_add(6.days())?))
+    }
+
+    /// Returns the ISO year and week number of the week, that of its middle day so that weeks not starting on Monday still get the number most of their days share
+    pub fn iso_week(&self) -> (i16, i8) {
+        let iso = self.start.saturating_add(3.days()).iso_week_date();
+        (iso.year(), iso.week())
+    }
 }
 
 impl std::fmt::Debug for SyrSpan {
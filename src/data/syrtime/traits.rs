@@ -1,19 +1,91 @@
+use crate::config::CONFIG;
+use clap::{ValueEnum, builder::PossibleValue};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// The format chosen with the --time-format flag, which takes precedence over the configuration
+pub static TIME_FORMAT: OnceLock<TimeFormat> = OnceLock::new();
+
+/// Determines how durations are displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeFormat {
+    #[serde(rename = "hh:mm:ss")]
+    HoursMinutesSeconds,
+    #[serde(rename = "hh:mm")]
+    HoursMinutes,
+    /// Hours with two decimals
+    #[serde(rename = "decimal")]
+    Decimal,
+    /// e.g. 3h 12m
+    #[serde(rename = "humanized")]
+    Humanized,
+}
+
+impl ValueEnum for TimeFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::HoursMinutesSeconds, Self::HoursMinutes, Self::Decimal, Self::Humanized]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::HoursMinutesSeconds => PossibleValue::new("hh:mm:ss").help("e.g. 03:12:45"),
+            Self::HoursMinutes => PossibleValue::new("hh:mm").help("e.g. 03:12"),
+            Self::Decimal => PossibleValue::new("decimal").alias("hours").help("e.g. 3.21"),
+            Self::Humanized => PossibleValue::new("humanized").alias("human").help("e.g. 3h 12m"),
+        })
+    }
+}
+
+impl TimeFormat {
+    /// Returns the format chosen with --time-format or in the configuration, None when neither was
+    pub fn current() -> Option<Self> {
+        TIME_FORMAT
+            .get()
+            .copied()
+            .or_else(|| CONFIG.get().and_then(|config| config.time_format))
+    }
+
+    pub fn format(&self, seconds: f64) -> String {
+        let total_s = seconds as u64;
+        let (hours, minutes, seconds_) = (total_s / 3_600, (total_s % 3_600) / 60, total_s % 60);
+        match self {
+            Self::HoursMinutesSeconds => format!("{:0>2}:{:0>2}:{:0>2}", hours, minutes, seconds_),
+            Self::HoursMinutes => format!("{:0>2}:{:0>2}", hours, minutes),
+            Self::Decimal => format!("{:.2}", seconds / 3600.0),
+            Self::Humanized => match (hours, minutes) {
+                (0, 0) => format!("{}s", seconds_),
+                (0, minutes) => format!("{}m", minutes),
+                (hours, minutes) => format!("{}h {:0>2}m", hours, minutes),
+            },
+        }
+    }
+
+    /// The length of durations under a hundred hours
+    pub fn width(&self) -> usize {
+        match self {
+            Self::HoursMinutesSeconds => 8,
+            Self::HoursMinutes | Self::Decimal => 5,
+            Self::Humanized => 7,
+        }
+    }
+}
+
 pub trait TimeFormatting {
-    const S_STR_LENGTH: usize = 8;
     const MS_STR_LENGTH: usize = 12;
+    /// Formats the duration following the chosen format, hh:mm:ss by default
     fn s_str(self) -> String;
     fn ms_str(self) -> String;
+    /// Formats a running duration following the chosen format, with milliseconds by default
+    fn live_str(self) -> String;
+    /// The length of durations under a hundred hours formatted by s_str
+    fn s_str_length() -> usize;
+    /// The length of durations under a hundred hours formatted by live_str
+    fn live_str_length() -> usize;
 }
 
 impl TimeFormatting for f64 {
     fn s_str(self) -> String {
-        let total_s = self as u64;
-
-        let hours = total_s / 3_600;
-        let minutes = (total_s % 3_600) / 60;
-        let seconds = total_s % 60;
-
-        format!("{:0>2}:{:0>2}:{:0>2}", hours, minutes, seconds)
+        TimeFormat::current().unwrap_or(TimeFormat::HoursMinutesSeconds).format(self)
     }
 
     fn ms_str(self) -> String {
@@ -26,6 +98,21 @@ impl TimeFormatting for f64 {
 
         format!("{:0>2}:{:0>2}:{:0>2}.{:0>3}", hours, minutes, seconds, millis)
     }
+
+    fn live_str(self) -> String {
+        match TimeFormat::current() {
+            Some(format) => format.format(self),
+            None => self.ms_str(),
+        }
+    }
+
+    fn s_str_length() -> usize {
+        TimeFormat::current().unwrap_or(TimeFormat::HoursMinutesSeconds).width()
+    }
+
+    fn live_str_length() -> usize {
+        TimeFormat::current().map(|format| format.width()).unwrap_or(Self::MS_STR_LENGTH)
+    }
 }
 
 pub trait WeekdayFormatting {
@@ -46,3 +133,18 @@ impl WeekdayFormatting for jiff::civil::Weekday {
         .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_formats() {
+        let seconds = 3.0 * 3600.0 + 8.0 * 60.0 + 45.0;
+        assert_eq!(TimeFormat::HoursMinutesSeconds.format(seconds), "03:08:45");
+        assert_eq!(TimeFormat::HoursMinutes.format(seconds), "03:08");
+        assert_eq!(TimeFormat::Decimal.format(seconds), "3.15");
+        assert_eq!(TimeFormat::Humanized.format(seconds), "3h 08m");
+        assert_eq!(TimeFormat::Humanized.format(12.0 * 60.0), "12m");
+    }
+}
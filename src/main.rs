@@ -4,6 +4,8 @@ mod cli;
 mod config;
 mod data;
 mod dirs;
+mod output;
+mod picker;
 mod utils;
 
 use color_eyre::{
@@ -44,9 +46,12 @@ fn main() -> Result<()> {
         }
     };
 
+    data::syrtime::TODAY
+        .set(date)
+        .map_err(|_| eyre!("Failed to lock the current date"))?;
+
     let entries = Entries::load()?;
 
-    println!();
     cli::cli(entries, date, datetime)?;
 
     Ok(())
@@ -0,0 +1,220 @@
+use crate::{
+    config::Config,
+    data::{Entry, syrtime::SyrDate},
+};
+use clap::{ArgMatches, ValueEnum, builder::PossibleValue};
+use color_eyre::Result;
+use itertools::Itertools;
+use serde::Serialize;
+use std::io::Write;
+
+/// Determines how reporting commands print their results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Colored human-readable text, the default
+    #[default]
+    Pretty,
+    /// Tab-separated values without a header
+    Plain,
+    Json,
+    Csv,
+}
+
+impl ValueEnum for OutputFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Pretty, Self::Plain, Self::Json, Self::Csv]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Pretty => PossibleValue::new("pretty").help("Colored human-readable text"),
+            Self::Plain => PossibleValue::new("plain").help("Tab-separated values without a header"),
+            Self::Json => PossibleValue::new("json").help("An array of JSON objects"),
+            Self::Csv => PossibleValue::new("csv").help("Comma-separated values with a header"),
+        })
+    }
+}
+
+impl OutputFormat {
+    pub fn from_arg_matches(arg_matches: &ArgMatches) -> Self {
+        arg_matches
+            .try_get_one::<Self>("output")
+            .ok()
+            .flatten()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        matches!(self, Self::Pretty)
+    }
+}
+
+/// A single line of machine-readable output, the schema is shared by every reporting command.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub name: &'a str,
+    pub aliases: &'a [String],
+    /// ISO 8601 date (yyyy-mm-dd), absent when the record spans multiple days
+    pub date: Option<String>,
+    pub seconds: f64,
+    /// Only present when billing rounding is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billable_seconds: Option<f64>,
+    /// Only present for goals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_seconds: Option<f64>,
+    /// Only present for goals, whether the target was reached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub met: Option<bool>,
+    /// Only present for comparisons, the time tracked over the span compared against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline_seconds: Option<f64>,
+    /// Only present for comparisons, the relative change in percent, null when nothing was tracked over the baseline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change: Option<Option<f64>>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(entry: &'a Entry, date: Option<&SyrDate>, seconds: f64) -> Self {
+        Self {
+            name: entry.name.as_str(),
+            aliases: entry.aliases.as_slice(),
+            date: date.map(|date| date.as_string_iso()),
+            seconds,
+            billable_seconds: None,
+            target_seconds: None,
+            met: None,
+            baseline_seconds: None,
+            change: None,
+        }
+    }
+
+    pub fn with_billable(self, billable_seconds: f64) -> Self {
+        Self {
+            billable_seconds: Some(billable_seconds),
+            ..self
+        }
+    }
+
+    pub fn with_target(self, target_seconds: f64) -> Self {
+        Self {
+            target_seconds: Some(target_seconds),
+            met: Some(self.seconds >= target_seconds),
+            ..self
+        }
+    }
+
+    pub fn with_baseline(self, baseline_seconds: f64, change: Option<f64>) -> Self {
+        Self {
+            baseline_seconds: Some(baseline_seconds),
+            change: Some(change),
+            ..self
+        }
+    }
+
+    /// The optional fields that are set, each preceded by the separator
+    fn extra_fields(&self, separator: char) -> String {
+        let mut fields = String::new();
+        if let Some(seconds) = self.billable_seconds {
+            fields += &format!("{}{}", separator, seconds);
+        }
+        if let (Some(target_seconds), Some(met)) = (self.target_seconds, self.met) {
+            fields += &format!("{}{}{}{}", separator, target_seconds, separator, met);
+        }
+        if let (Some(baseline_seconds), Some(change)) = (self.baseline_seconds, self.change) {
+            let change = change.map(|change| change.to_string()).unwrap_or_default();
+            fields += &format!("{}{}{}{}", separator, baseline_seconds, separator, change);
+        }
+        fields
+    }
+}
+
+/// Sorts the records by date, then following the configured sort option.
+pub fn sort(records: &mut [Record]) {
+    let sort_option = Config::get().sort_option;
+    records.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then(sort_option.compare((a.name, a.seconds), (b.name, b.seconds)))
+    });
+}
+
+/// Writes the records to stdout in the requested format, does nothing for `OutputFormat::Pretty`.
+pub fn emit(format: OutputFormat, records: &[Record]) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match format {
+        OutputFormat::Pretty => (),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, records)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Csv => {
+            let billable = records.iter().any(|record| record.billable_seconds.is_some());
+            let goals = records.iter().any(|record| record.target_seconds.is_some());
+            let comparisons = records.iter().any(|record| record.baseline_seconds.is_some());
+            writeln!(
+                stdout,
+                "name,aliases,date,seconds{}{}{}",
+                if billable { ",billable_seconds" } else { "" },
+                if goals { ",target_seconds,met" } else { "" },
+                if comparisons { ",baseline_seconds,change" } else { "" }
+            )?;
+            for record in records {
+                writeln!(
+                    stdout,
+                    "{},{},{},{}{}",
+                    csv_escape(record.name),
+                    csv_escape(&record.aliases.join(";")),
+                    record.date.as_deref().unwrap_or_default(),
+                    record.seconds,
+                    record.extra_fields(',')
+                )?;
+            }
+        }
+        OutputFormat::Plain => {
+            for record in records {
+                writeln!(
+                    stdout,
+                    "{}\t{}\t{}\t{}{}",
+                    record.name,
+                    record.aliases.join(";"),
+                    record.date.as_deref().unwrap_or_default(),
+                    record.seconds,
+                    record.extra_fields('\t')
+                )?;
+            }
+        }
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Prints rows that do not fit the record schema, with a header for csv, json being left to serde
+pub fn emit_rows(format: OutputFormat, header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match format {
+        OutputFormat::Pretty | OutputFormat::Json => (),
+        OutputFormat::Csv => {
+            writeln!(stdout, "{}", header.join(","))?;
+            for row in rows {
+                writeln!(stdout, "{}", row.iter().map(|field| csv_escape(field)).join(","))?;
+            }
+        }
+        OutputFormat::Plain => {
+            for row in rows {
+                writeln!(stdout, "{}", row.join("\t"))?;
+            }
+        }
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+pub fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
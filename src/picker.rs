@@ -0,0 +1,179 @@
+use crate::{
+    algorithms::Matcher,
+    config::Config,
+    data::{
+        Entry,
+        syrtime::{SyrDate, TimeFormatting},
+    },
+    utils::{ARROWHEAD, enter_fullscreen_mode, exit_fullscreen_mode},
+};
+use crossterm::{
+    cursor, event, queue,
+    style::{Print, Stylize},
+    terminal,
+};
+use itertools::Itertools;
+use std::io::{self, Write};
+
+/// A full-screen, type-to-filter list of entries ranked by the matcher
+pub struct Picker<'a> {
+    entries: Vec<&'a Entry>,
+    today: &'a SyrDate,
+    matcher: Matcher,
+    query: String,
+    ranked: Vec<&'a Entry>,
+    selected: usize,
+    offset: usize,
+}
+
+impl<'a> Picker<'a> {
+    pub fn new(entries: Vec<&'a Entry>, query: &str, today: &'a SyrDate) -> Self {
+        let mut picker = Self {
+            entries,
+            today,
+            matcher: Matcher::new(Config::get()),
+            query: query.to_string(),
+            ranked: Vec::new(),
+            selected: 0,
+            offset: 0,
+        };
+        picker.rank();
+        picker
+    }
+
+    /// Runs the picker until an entry is selected or the user cancels
+    pub fn pick(self) -> Option<Entry> {
+        enter_fullscreen_mode();
+        let response = self.run();
+        exit_fullscreen_mode();
+        response
+    }
+
+    /// Runs the picker on a screen that is already in fullscreen mode
+    pub fn run(mut self) -> Option<Entry> {
+        let mut stderr = io::stderr();
+        loop {
+            if let Err(err) = self.draw(&mut stderr) {
+                eprintln!("Warning: Failed to draw the picker, '{}'", err);
+            }
+            let key = match event::read() {
+                Ok(event::Event::Key(key)) => key,
+                Ok(_) => continue,
+                Err(err) => {
+                    eprintln!("Warning: Event read issue, '{}'", err);
+                    continue;
+                }
+            };
+
+            if key.kind != event::KeyEventKind::Press {
+                continue;
+            }
+
+            let ctrl = key.modifiers.contains(event::KeyModifiers::CONTROL);
+            match key.code {
+                event::KeyCode::Esc => break None,
+                event::KeyCode::Char('c') if ctrl => break None,
+                event::KeyCode::Enter => break self.ranked.get(self.selected).map(|entry| (*entry).clone()),
+                event::KeyCode::Up | event::KeyCode::BackTab => self.selected = self.selected.saturating_sub(1),
+                event::KeyCode::Char('p') if ctrl => self.selected = self.selected.saturating_sub(1),
+                event::KeyCode::Down | event::KeyCode::Tab => self.select_next(),
+                event::KeyCode::Char('n') if ctrl => self.select_next(),
+                event::KeyCode::Backspace => {
+                    self.query.pop();
+                    self.rank();
+                }
+                event::KeyCode::Char(chr) if !ctrl => {
+                    self.query.extend(chr.to_uppercase());
+                    self.rank();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn select_next(&mut self) {
+        if self.selected + 1 < self.ranked.len() {
+            self.selected += 1
+        }
+    }
+
+    /// Without a query the most active entries come first, otherwise entries are sorted by score
+    fn rank(&mut self) {
+        let threshold = Config::get().search_threshold;
+        self.ranked = if self.query.is_empty() {
+            self.entries
+                .iter()
+                .map(|entry| (self.matcher.activity_boost(&entry.blocs, self.today), *entry))
+                .sorted_by(|(a, entry_a), (b, entry_b)| b.total_cmp(a).then(entry_a.name.cmp(&entry_b.name)))
+                .map(|(_, entry)| entry)
+                .collect()
+        } else {
+            self.entries
+                .iter()
+                .map(|entry| (self.matcher.score_entry(entry, &self.query, self.today), *entry))
+                .filter(|(score, _)| *score >= threshold)
+                .sorted_by(|(a, _), (b, _)| b.total_cmp(a))
+                .map(|(_, entry)| entry)
+                .collect()
+        };
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    fn draw(&mut self, stderr: &mut io::Stderr) -> io::Result<()> {
+        // some pseudo-terminals report a size of zero
+        let (columns, rows) = match terminal::size()? {
+            (0, _) | (_, 0) => (80, 24),
+            size => size,
+        };
+        let visible = (rows as usize).saturating_sub(3).max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + visible {
+            self.offset = self.selected + 1 - visible;
+        }
+
+        let titles = self
+            .ranked
+            .iter()
+            .skip(self.offset)
+            .take(visible)
+            .map(|entry| title(entry))
+            .collect_vec();
+        let pad = titles
+            .iter()
+            .map(|title| title.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min((columns as usize).saturating_sub(f64::s_str_length() + 3));
+
+        queue!(
+            stderr,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0),
+            Print(format!("{} {}", ARROWHEAD.cyan(), self.query.as_str().bold())),
+            cursor::MoveTo(0, 1),
+            Print(format!("{}/{}", self.ranked.len(), self.entries.len()).dim()),
+        )?;
+
+        for (row, ((idx, entry), title)) in self.ranked.iter().enumerate().skip(self.offset).zip(titles).enumerate() {
+            let title: String = format!("{:<pad$}", title).chars().take(pad).collect();
+            let duration = entry.get_bloc_duration(self.today).s_str();
+            let line = if idx == self.selected {
+                format!("{} {} {}", ARROWHEAD.cyan(), title.bold(), duration.bold())
+            } else {
+                format!("  {} {}", title, duration.dim())
+            };
+            queue!(stderr, cursor::MoveTo(0, row as u16 + 2), Print(line))?;
+        }
+        stderr.flush()
+    }
+}
+
+/// The name followed by the aliases, without any styling so that it can be padded
+fn title(entry: &Entry) -> String {
+    match entry.aliases.len() {
+        0 => entry.name.clone(),
+        1.. => format!("{}; {}", entry.name, entry.aliases.join(", ")),
+    }
+}
@@ -1,19 +1,40 @@
 // Imports
 use crossterm::{
     cursor, execute,
-    terminal::{disable_raw_mode, enable_raw_mode},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use std::io::stdout;
+use std::io::stderr;
 
 pub static ARROW: &str = "━━⮞";
 pub static ARROWHEAD: &str = "⮞";
+static EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
 
 pub fn enter_clean_input_mode() {
     let _ = enable_raw_mode().map_err(|err| eprintln!("Warning, Failed to enable raw mode: '{err}'"));
-    let _ = execute!(stdout(), cursor::Hide).map_err(|err| eprintln!("Warning, Failed to hide cursor: '{err}'"));
+    let _ = execute!(stderr(), cursor::Hide).map_err(|err| eprintln!("Warning, Failed to hide cursor: '{err}'"));
 }
 
 pub fn exit_clean_input_mode() {
-    let _ = execute!(stdout(), cursor::Show).map_err(|err| eprintln!("Warning: Failed to show cursor: '{err}'"));
+    let _ = execute!(stderr(), cursor::Show).map_err(|err| eprintln!("Warning: Failed to show cursor: '{err}'"));
     let _ = disable_raw_mode().map_err(|err| eprintln!("Warning: Failed to disable raw mode: '{err}'"));
 }
+
+pub fn enter_fullscreen_mode() {
+    let _ = execute!(stderr(), EnterAlternateScreen).map_err(|err| eprintln!("Warning: Failed to enter the alternate screen: '{err}'"));
+    enter_clean_input_mode();
+}
+
+pub fn exit_fullscreen_mode() {
+    exit_clean_input_mode();
+    let _ = execute!(stderr(), LeaveAlternateScreen).map_err(|err| eprintln!("Warning: Failed to leave the alternate screen: '{err}'"));
+}
+
+/// Returns a bar made of block characters filling the given fraction of the width, with a resolution of an eighth of a character
+pub fn horizontal_bar(fraction: f64, width: usize) -> String {
+    let eighths = (fraction.clamp(0.0, 1.0) * (width * 8) as f64).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(EIGHTHS[eighths % 8]);
+    }
+    format!("{:<width$}", bar)
+}